@@ -0,0 +1,42 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+
+use nerdle_server::{relay, AppState};
+
+const USAGE: &str = "usage: nerdle-relay [--listen ADDR] [--data DIR]";
+
+struct Args {
+    listen: SocketAddr,
+    data_dir: PathBuf,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args {
+        listen: "127.0.0.1:8082".parse()?,
+        data_dir: PathBuf::from("ui/data"),
+    };
+    let mut argv = std::env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        let mut value = || argv.next().ok_or_else(|| anyhow!(USAGE));
+        match arg.as_str() {
+            "--listen" => args.listen = value()?.parse()?,
+            "--data" => args.data_dir = value()?.into(),
+            _ => return Err(anyhow!(USAGE)),
+        }
+    }
+    Ok(args)
+}
+
+/// Relay of race rooms only. The word lists of the data
+/// directory are used to pick the solutions.
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = parse_args()?;
+    let state = AppState::new(args.data_dir);
+
+    let listener = tokio::net::TcpListener::bind(args.listen).await?;
+    println!("relaying races on ws://{}", args.listen);
+    axum::serve(listener, relay(state)).await?;
+    Ok(())
+}
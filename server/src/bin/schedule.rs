@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, Utc};
+
+use nerdle_server::schedule::{write_schedule, ScheduleOptions};
+
+const USAGE: &str = "usage: nerdle-schedule --answers FILE [--data DIR] \
+    [--start YYYY-MM-DD] [--days N] [--seed N] [--editor NAME]";
+
+struct Args {
+    answers: Option<PathBuf>,
+    data_dir: PathBuf,
+    options: ScheduleOptions,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args {
+        answers: None,
+        data_dir: PathBuf::from("ui/data"),
+        options: ScheduleOptions {
+            start: Utc::now().date_naive(),
+            days: 365,
+            seed: 0,
+            editor: "nerdle".into(),
+        },
+    };
+    let mut argv = std::env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        let mut value = || argv.next().ok_or_else(|| anyhow!(USAGE));
+        match arg.as_str() {
+            "--answers" => args.answers = Some(value()?.into()),
+            "--data" => args.data_dir = value()?.into(),
+            "--start" => {
+                args.options.start =
+                    NaiveDate::parse_from_str(&value()?, "%Y-%m-%d")?
+            }
+            "--days" => args.options.days = value()?.parse()?,
+            "--seed" => args.options.seed = value()?.parse()?,
+            "--editor" => args.options.editor = value()?,
+            _ => return Err(anyhow!(USAGE)),
+        }
+    }
+    Ok(args)
+}
+
+fn main() -> Result<()> {
+    let args = parse_args()?;
+    let answers_path = args.answers.ok_or_else(|| anyhow!(USAGE))?;
+    let answers = std::fs::read_to_string(answers_path)?;
+    let paths = write_schedule(&args.data_dir, &answers, &args.options)?;
+    println!(
+        "wrote {} puzzles to {}",
+        paths.len(),
+        args.data_dir.display()
+    );
+    Ok(())
+}
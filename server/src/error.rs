@@ -0,0 +1,56 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+#[derive(Debug)]
+pub enum ApiError {
+    NotFound,
+    BadRequest(String),
+    Forbidden(String),
+    /// A well-formed request the game rules don't allow,
+    /// like a guess which is not in the word list
+    Rejected(String),
+    /// The server is at capacity, the request may succeed later
+    Unavailable(String),
+    Internal(anyhow::Error),
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
+            ApiError::NotFound => (StatusCode::NOT_FOUND, "not found".into()),
+            ApiError::BadRequest(reason) => (StatusCode::BAD_REQUEST, reason),
+            ApiError::Forbidden(reason) => (StatusCode::FORBIDDEN, reason),
+            ApiError::Rejected(reason) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, reason)
+            }
+            ApiError::Unavailable(reason) => {
+                (StatusCode::SERVICE_UNAVAILABLE, reason)
+            }
+            ApiError::Internal(err) => {
+                eprintln!("internal error: {:?}", err);
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal error".into())
+            }
+        };
+        (status, Json(ErrorBody { error })).into_response()
+    }
+}
+
+impl<E> From<E> for ApiError
+where
+    E: Into<anyhow::Error>,
+{
+    fn from(err: E) -> Self {
+        ApiError::Internal(err.into())
+    }
+}
+
+pub type ApiResult<T> = Result<T, ApiError>;
@@ -0,0 +1,329 @@
+use std::{path::Path as FilePath, sync::Mutex};
+
+use anyhow::Result;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use rusqlite::{params, Connection};
+use serde::Deserialize;
+
+use nerdle_ui::{
+    api::{clean_name, DailyRank, Leaderboard, OverallRank, ResultSubmission},
+    state::Language,
+};
+
+use crate::{
+    error::{ApiError, ApiResult},
+    puzzles::LanguageQuery,
+    AppState,
+};
+
+const MAX_TRIES: usize = 6;
+
+/// Number of players ranked
+const MAX_RANKS: usize = 50;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS results (
+    team TEXT NOT NULL,
+    nickname TEXT NOT NULL,
+    language TEXT NOT NULL,
+    puzzle INTEGER NOT NULL,
+    tries INTEGER NOT NULL,
+    solved INTEGER NOT NULL,
+    time INTEGER,
+    hard_mode INTEGER NOT NULL,
+    PRIMARY KEY (team, nickname, language, puzzle)
+);
+";
+
+/// Results of the daily puzzles by group, in SQLite
+pub struct LeaderboardStore {
+    db: Mutex<Connection>,
+}
+
+impl LeaderboardStore {
+    pub fn open(path: &FilePath) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// A store which is gone with the server
+    pub fn in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(db: Connection) -> Result<Self> {
+        db.execute_batch(SCHEMA)?;
+        Ok(Self { db: Mutex::new(db) })
+    }
+
+    /// Store a result. Only the first result of a player
+    /// for a puzzle counts.
+    pub fn submit(
+        &self,
+        team: &str,
+        language: &Language,
+        result: &ResultSubmission,
+    ) -> Result<()> {
+        self.db.lock().unwrap().execute(
+            "INSERT OR IGNORE INTO results
+                (team, nickname, language, puzzle, tries, solved, time, hard_mode)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                team,
+                result.nickname,
+                language.code(),
+                result.id,
+                result.tries,
+                result.solved,
+                result.time,
+                result.hard_mode,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Best first: solved, in fewer tries, faster
+    pub fn daily(
+        &self,
+        team: &str,
+        language: &Language,
+        puzzle: u32,
+    ) -> Result<Vec<DailyRank>> {
+        let db = self.db.lock().unwrap();
+        let mut query = db.prepare(
+            "SELECT nickname, tries, solved, time, hard_mode FROM results
+             WHERE team = ?1 AND language = ?2 AND puzzle = ?3
+             ORDER BY solved DESC, tries, time IS NULL, time, nickname
+             LIMIT ?4",
+        )?;
+        let ranks = query
+            .query_map(
+                params![team, language.code(), puzzle, MAX_RANKS],
+                |row| {
+                    Ok(DailyRank {
+                        nickname: row.get(0)?,
+                        tries: row.get(1)?,
+                        solved: row.get(2)?,
+                        time: row.get(3)?,
+                        hard_mode: row.get(4)?,
+                    })
+                },
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(ranks)
+    }
+
+    /// Best first: most puzzles solved, in the fewest tries
+    pub fn all_time(
+        &self,
+        team: &str,
+        language: &Language,
+    ) -> Result<Vec<OverallRank>> {
+        let db = self.db.lock().unwrap();
+        let mut query = db.prepare(
+            "SELECT nickname, COUNT(*), SUM(solved),
+                    AVG(CASE WHEN solved THEN tries END) AS average
+             FROM results
+             WHERE team = ?1 AND language = ?2
+             GROUP BY nickname
+             ORDER BY SUM(solved) DESC, average IS NULL, average, nickname
+             LIMIT ?3",
+        )?;
+        let ranks = query
+            .query_map(params![team, language.code(), MAX_RANKS], |row| {
+                Ok(OverallRank {
+                    nickname: row.get(0)?,
+                    played: row.get(1)?,
+                    solved: row.get(2)?,
+                    average_tries: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(ranks)
+    }
+}
+
+/// Groups are named like players
+fn team_name(group: &str) -> ApiResult<String> {
+    match clean_name(group) {
+        name if name.is_empty() || name != group => {
+            Err(ApiError::BadRequest(format!("invalid group: {}", group)))
+        }
+        name => Ok(name),
+    }
+}
+
+async fn submit(
+    State(state): State<AppState>,
+    Path(group): Path<String>,
+    Query(query): Query<LanguageQuery>,
+    Json(mut result): Json<ResultSubmission>,
+) -> ApiResult<StatusCode> {
+    let team = team_name(&group)?;
+    let language = query.language()?;
+    result.nickname = clean_name(&result.nickname);
+    if result.nickname.is_empty() {
+        return Err(ApiError::BadRequest("missing nickname".into()));
+    }
+    if result.tries == 0 || result.tries > MAX_TRIES {
+        return Err(ApiError::BadRequest("invalid tries".into()));
+    }
+    if result.time.is_some_and(|time| time < 0) {
+        return Err(ApiError::BadRequest("invalid time".into()));
+    }
+    state
+        .puzzles
+        .by_id(&language, result.id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    state.leaderboard.submit(&team, &language, &result)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+struct LeaderboardQuery {
+    lang: Option<String>,
+    /// Puzzle of the daily ranking
+    id: u32,
+}
+
+async fn leaderboard(
+    State(state): State<AppState>,
+    Path(group): Path<String>,
+    Query(query): Query<LeaderboardQuery>,
+) -> ApiResult<Json<Leaderboard>> {
+    let team = team_name(&group)?;
+    let language = LanguageQuery { lang: query.lang }.language()?;
+    Ok(Json(Leaderboard {
+        today: state.leaderboard.daily(&team, &language, query.id)?,
+        all_time: state.leaderboard.all_time(&team, &language)?,
+    }))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/leaderboard/:group", get(leaderboard))
+        .route("/leaderboard/:group/results", post(submit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solved(nickname: &str, id: u32, tries: usize) -> ResultSubmission {
+        ResultSubmission {
+            nickname: nickname.into(),
+            id,
+            tries,
+            solved: true,
+            time: None,
+            hard_mode: false,
+        }
+    }
+
+    fn lost(nickname: &str, id: u32) -> ResultSubmission {
+        ResultSubmission {
+            solved: false,
+            ..solved(nickname, id, MAX_TRIES)
+        }
+    }
+
+    fn timed(result: ResultSubmission, time: i64) -> ResultSubmission {
+        ResultSubmission {
+            time: Some(time),
+            ..result
+        }
+    }
+
+    #[test]
+    fn ranks_solved_games_by_tries_then_time() {
+        let store = LeaderboardStore::in_memory().unwrap();
+        let english = Language::English;
+        for result in [
+            timed(lost("lost", 1), 1_000),
+            solved("untimed", 1, 3),
+            timed(solved("slow", 1, 3), 90_000),
+            timed(solved("fast", 1, 3), 30_000),
+            timed(solved("lucky", 1, 2), 60_000),
+        ] {
+            store.submit("team", &english, &result).unwrap();
+        }
+        let other = solved("elsewhere", 1, 1);
+        store.submit("other", &english, &other).unwrap();
+        let german = solved("german", 1, 1);
+        store.submit("team", &Language::German, &german).unwrap();
+
+        let ranks = store.daily("team", &english, 1).unwrap();
+        let nicknames: Vec<&str> =
+            ranks.iter().map(|rank| rank.nickname.as_str()).collect();
+        assert_eq!(nicknames, ["lucky", "fast", "slow", "untimed", "lost"]);
+    }
+
+    #[test]
+    fn keeps_the_first_result_of_a_player() {
+        let store = LeaderboardStore::in_memory().unwrap();
+        let english = Language::English;
+        for result in [solved("ann", 1, 5), solved("ann", 1, 1)] {
+            store.submit("team", &english, &result).unwrap();
+        }
+        store
+            .submit("team", &english, &solved("ann", 2, 1))
+            .unwrap();
+
+        let ranks = store.daily("team", &english, 1).unwrap();
+        assert_eq!(ranks.len(), 1);
+        assert_eq!(ranks[0].tries, 5);
+    }
+
+    #[test]
+    fn averages_the_tries_of_solved_games_only() {
+        let store = LeaderboardStore::in_memory().unwrap();
+        let english = Language::English;
+        for result in [
+            solved("ann", 1, 2),
+            solved("ann", 2, 4),
+            lost("ann", 3),
+            solved("bob", 1, 3),
+            solved("bob", 2, 4),
+            lost("cid", 1),
+        ] {
+            store.submit("team", &english, &result).unwrap();
+        }
+
+        let ranks = store.all_time("team", &english).unwrap();
+        let summary: Vec<_> = ranks
+            .iter()
+            .map(|rank| {
+                let OverallRank {
+                    nickname,
+                    played,
+                    solved,
+                    average_tries,
+                } = rank;
+                (nickname.as_str(), *played, *solved, *average_tries)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("ann", 3, 2, Some(3.0)),
+                ("bob", 2, 2, Some(3.5)),
+                ("cid", 1, 0, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_only_clean_group_names() {
+        assert_eq!(team_name("friends42").unwrap(), "friends42");
+        let long = "x".repeat(17);
+        for group in ["", "two words", "a/b", &long] {
+            let result = team_name(group);
+            assert!(matches!(result, Err(ApiError::BadRequest(_))), "{group}");
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! HTTP API of nerdle:
+//!
+//! - `GET /api/v1/puzzles/today?lang=en`: puzzle of the day
+//! - `GET /api/v1/puzzles/date/:date?lang=en`: puzzle by `YYYY-MM-DD`
+//! - `GET /api/v1/puzzles/id/:id?lang=en`: puzzle by id
+//!
+//!   The puzzles come with a blank solution, unless the server
+//!   runs with `--expose-solutions`.
+//! - `GET /api/v1/wordlists`: languages with word list versions
+//! - `GET /api/v1/wordlists/:lang`: the word list as text
+//! - `GET /api/v1/play/today?lang=en`: puzzle of the day without solution
+//! - `GET /api/v1/play/date/:date?lang=en`: earlier puzzle without solution
+//! - `POST /api/v1/play/:id/guess?lang=en`: score a guess
+//! - `POST /api/v1/play/:id/reveal?lang=en`: solution of a finished game
+//! - `POST /api/v1/reports`: store an error report of the ui
+//! - `GET /api/v1/reports`: the latest error reports
+//! - `POST /api/v1/leaderboard/:group/results?lang=en`: submit a daily result
+//! - `GET /api/v1/leaderboard/:group?lang=en&id=`: rankings of a group
+//! - `POST /api/v1/sync/:code`: merge the games of a device with the stored ones
+//! - `POST /api/v1/race?lang=en`: open a race room on a random word
+//! - `GET /api/v1/race/:code?name=`: join a race room over WebSocket
+use std::{path::PathBuf, sync::Arc};
+
+use axum::Router;
+use tower_http::cors::CorsLayer;
+
+pub mod error;
+pub mod leaderboard;
+pub mod play;
+pub mod puzzles;
+pub mod race;
+pub mod reports;
+pub mod schedule;
+pub mod sync;
+
+use leaderboard::LeaderboardStore;
+use puzzles::PuzzleStore;
+use race::RoomStore;
+use reports::ReportStore;
+use sync::SyncStore;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub puzzles: Arc<PuzzleStore>,
+    /// Serve the solutions from the puzzle endpoints. Off by
+    /// default, so guesses can only be scored through `/play`.
+    pub expose_solutions: bool,
+    pub reports: Arc<ReportStore>,
+    pub rooms: Arc<RoomStore>,
+    pub leaderboard: Arc<LeaderboardStore>,
+    pub sync: Arc<SyncStore>,
+}
+
+impl AppState {
+    pub fn new(data_dir: PathBuf) -> Self {
+        Self {
+            puzzles: Arc::new(PuzzleStore::new(data_dir)),
+            expose_solutions: false,
+            reports: Arc::default(),
+            rooms: Arc::default(),
+            leaderboard: Arc::new(
+                LeaderboardStore::in_memory()
+                    .expect("failed to create the leaderboard"),
+            ),
+            sync: Arc::new(
+                SyncStore::in_memory()
+                    .expect("failed to create the sync store"),
+            ),
+        }
+    }
+}
+
+/// Build the router of the API.
+pub fn app(state: AppState) -> Router {
+    Router::new()
+        .nest(
+            "/api/v1",
+            puzzles::routes()
+                .merge(play::routes())
+                .merge(reports::routes())
+                .merge(leaderboard::routes())
+                .merge(sync::routes())
+                .merge(race::routes()),
+        )
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
+
+/// Build a router with the race rooms only, to relay
+/// races without serving the puzzles.
+pub fn relay(state: AppState) -> Router {
+    Router::new()
+        .nest("/api/v1", race::routes())
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
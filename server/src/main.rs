@@ -0,0 +1,59 @@
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use anyhow::{anyhow, Result};
+
+use nerdle_server::{
+    app, leaderboard::LeaderboardStore, sync::SyncStore, AppState,
+};
+
+const USAGE: &str = "usage: nerdle-server [--listen ADDR] [--data DIR] \
+    [--expose-solutions] [--leaderboard FILE] [--sync FILE]";
+
+struct Args {
+    listen: SocketAddr,
+    data_dir: PathBuf,
+    expose_solutions: bool,
+    /// SQLite database of the leaderboard
+    leaderboard: PathBuf,
+    /// SQLite database of the synced games
+    sync: PathBuf,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args {
+        listen: "127.0.0.1:8081".parse()?,
+        data_dir: PathBuf::from("ui/data"),
+        expose_solutions: false,
+        leaderboard: PathBuf::from("leaderboard.db"),
+        sync: PathBuf::from("sync.db"),
+    };
+    let mut argv = std::env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        let mut value = || argv.next().ok_or_else(|| anyhow!(USAGE));
+        match arg.as_str() {
+            "--listen" => args.listen = value()?.parse()?,
+            "--data" => args.data_dir = value()?.into(),
+            "--expose-solutions" => args.expose_solutions = true,
+            "--leaderboard" => args.leaderboard = value()?.into(),
+            "--sync" => args.sync = value()?.into(),
+            _ => return Err(anyhow!(USAGE)),
+        }
+    }
+    Ok(args)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = parse_args()?;
+    let state = AppState {
+        expose_solutions: args.expose_solutions,
+        leaderboard: Arc::new(LeaderboardStore::open(&args.leaderboard)?),
+        sync: Arc::new(SyncStore::open(&args.sync)?),
+        ..AppState::new(args.data_dir)
+    };
+
+    let listener = tokio::net::TcpListener::bind(args.listen).await?;
+    println!("listening on http://{}", args.listen);
+    axum::serve(listener, app(state)).await?;
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{NaiveDate, Utc};
+
+use nerdle_ui::{
+    api::{
+        GuessRequest, GuessResponse, Puzzle, RevealRequest, RevealResponse,
+        Wordle,
+    },
+    state::{Guess, Language, Wordlist},
+};
+
+use crate::{
+    error::{ApiError, ApiResult},
+    puzzles::LanguageQuery,
+    AppState,
+};
+
+const MAX_TRIES: usize = 6;
+
+/// Load a puzzle and the word list of its language to
+/// score guesses against. Puzzles of the future are not
+/// found, as in the archive.
+async fn load(
+    state: &AppState,
+    language: &Language,
+    id: u32,
+) -> ApiResult<(Wordle, Wordlist)> {
+    let mut wordle = state
+        .puzzles
+        .by_id(language, id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let date = NaiveDate::parse_from_str(&wordle.print_date, "%Y-%m-%d")?;
+    if date > Utc::now().date_naive() {
+        return Err(ApiError::NotFound);
+    }
+    wordle.solution = language.fold(&wordle.solution);
+    let wordlist = Wordlist::from(state.puzzles.wordlist(language).await?);
+    Ok((wordle, wordlist))
+}
+
+/// Check a guess against the word list and the length
+/// of the solution.
+fn validate(
+    language: &Language,
+    wordle: &Wordle,
+    wordlist: &Wordlist,
+    guess: &Guess,
+) -> ApiResult<Guess> {
+    let guess = Guess::from(language.fold(&guess.to_string()));
+    if guess.len() != wordle.solution.chars().count() {
+        return Err(ApiError::Rejected("wrong length".into()));
+    }
+    if !wordlist.contains(&guess.to_string()) {
+        return Err(ApiError::Rejected("not a word".into()));
+    }
+    Ok(guess)
+}
+
+async fn puzzle(
+    state: &AppState,
+    language: &Language,
+    date: NaiveDate,
+) -> ApiResult<Json<Puzzle>> {
+    let wordle = state.puzzles.by_date(language, date).await?;
+    let wordle = wordle.ok_or(ApiError::NotFound)?;
+    Ok(Json(Puzzle::from(&wordle)))
+}
+
+async fn today(
+    State(state): State<AppState>,
+    Query(query): Query<LanguageQuery>,
+) -> ApiResult<Json<Puzzle>> {
+    let date = Utc::now().date_naive();
+    puzzle(&state, &query.language()?, date).await
+}
+
+/// Puzzles of the archive. Puzzles of the future are kept
+/// secret, like their solutions.
+async fn by_date(
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+    Query(query): Query<LanguageQuery>,
+) -> ApiResult<Json<Puzzle>> {
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|_| ApiError::BadRequest(format!("invalid date: {}", date)))?;
+    if date > Utc::now().date_naive() {
+        return Err(ApiError::NotFound);
+    }
+    puzzle(&state, &query.language()?, date).await
+}
+
+async fn guess(
+    State(state): State<AppState>,
+    Path(id): Path<u32>,
+    Query(query): Query<LanguageQuery>,
+    Json(request): Json<GuessRequest>,
+) -> ApiResult<Json<GuessResponse>> {
+    let language = query.language()?;
+    let (wordle, wordlist) = load(&state, &language, id).await?;
+    let guess = validate(&language, &wordle, &wordlist, &request.guess)?;
+    let hints = guess.hints(&wordle.solution);
+    Ok(Json(GuessResponse { hints }))
+}
+
+/// The solution is only revealed for finished games:
+/// either solved or out of valid tries.
+async fn reveal(
+    State(state): State<AppState>,
+    Path(id): Path<u32>,
+    Query(query): Query<LanguageQuery>,
+    Json(request): Json<RevealRequest>,
+) -> ApiResult<Json<RevealResponse>> {
+    let language = query.language()?;
+    let (wordle, wordlist) = load(&state, &language, id).await?;
+    let guesses = request
+        .guesses
+        .iter()
+        .map(|guess| validate(&language, &wordle, &wordlist, guess))
+        .collect::<ApiResult<Vec<Guess>>>()?;
+    let solved = guesses.iter().any(|guess| guess.matches(&wordle.solution));
+    if !solved && guesses.len() < MAX_TRIES {
+        return Err(ApiError::Forbidden("game is not finished".into()));
+    }
+    Ok(Json(RevealResponse {
+        solution: wordle.solution,
+    }))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/play/today", get(today))
+        .route("/play/date/:date", get(by_date))
+        .route("/play/:id/guess", post(guess))
+        .route("/play/:id/reveal", post(reveal))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use axum::{http::StatusCode, response::IntoResponse};
+    use chrono::Days;
+
+    use super::*;
+
+    /// A data directory with a puzzle of the past (id 1,
+    /// solution BROTH) and one of tomorrow (id 2)
+    fn state(name: &str) -> AppState {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("nerdle-play-{}", name));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("words.txt"), "BROTH\nCRANE\nPLUMB\n").unwrap();
+        let tomorrow = Utc::now().date_naive() + Days::new(1);
+        for (id, date) in
+            [(1, "2024-01-01".to_string()), (2, tomorrow.to_string())]
+        {
+            let wordle = Wordle {
+                id,
+                print_date: date.clone(),
+                solution: "BROTH".into(),
+                ..Default::default()
+            };
+            let text = serde_json::to_string(&wordle).unwrap();
+            std::fs::write(dir.join(format!("{}.json", date)), text).unwrap();
+        }
+        AppState::new(dir)
+    }
+
+    fn query() -> Query<LanguageQuery> {
+        Query(LanguageQuery { lang: None })
+    }
+
+    fn status<T>(result: ApiResult<T>) -> StatusCode {
+        match result {
+            Ok(_) => StatusCode::OK,
+            Err(err) => err.into_response().status(),
+        }
+    }
+
+    async fn guess_word(state: &AppState, id: u32, word: &str) -> StatusCode {
+        let request = GuessRequest { guess: word.into() };
+        let result =
+            guess(State(state.clone()), Path(id), query(), Json(request)).await;
+        status(result)
+    }
+
+    async fn reveal_words(
+        state: &AppState,
+        id: u32,
+        words: &[&str],
+    ) -> StatusCode {
+        let guesses = words.iter().map(|&word| word.into()).collect();
+        let request = RevealRequest { guesses };
+        let result =
+            reveal(State(state.clone()), Path(id), query(), Json(request))
+                .await;
+        status(result)
+    }
+
+    #[tokio::test]
+    async fn rejects_guesses_outside_the_word_list() {
+        let state = state("guess");
+        assert_eq!(guess_word(&state, 1, "CRANE").await, StatusCode::OK);
+        assert_eq!(guess_word(&state, 1, "crane").await, StatusCode::OK);
+        let rejected = StatusCode::UNPROCESSABLE_ENTITY;
+        assert_eq!(guess_word(&state, 1, "ABCDE").await, rejected);
+        assert_eq!(guess_word(&state, 1, "CRAN").await, rejected);
+        assert_eq!(guess_word(&state, 1, "CRANES").await, rejected);
+        assert_eq!(guess_word(&state, 3, "CRANE").await, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn reveals_only_finished_games() {
+        let state = state("reveal");
+        let forbidden = StatusCode::FORBIDDEN;
+        assert_eq!(reveal_words(&state, 1, &[]).await, forbidden);
+        assert_eq!(reveal_words(&state, 1, &["CRANE"; 5]).await, forbidden);
+        assert_eq!(
+            reveal_words(&state, 1, &["CRANE"; 6]).await,
+            StatusCode::OK
+        );
+        let solved = ["CRANE", "BROTH"];
+        assert_eq!(reveal_words(&state, 1, &solved).await, StatusCode::OK);
+        // Invalid guesses do not count as tries
+        let rejected = StatusCode::UNPROCESSABLE_ENTITY;
+        assert_eq!(reveal_words(&state, 1, &["ABCDE"; 6]).await, rejected);
+    }
+
+    #[tokio::test]
+    async fn keeps_future_puzzles_secret() {
+        let state = state("future");
+        let not_found = StatusCode::NOT_FOUND;
+        assert_eq!(guess_word(&state, 2, "CRANE").await, not_found);
+        assert_eq!(reveal_words(&state, 2, &["BROTH"]).await, not_found);
+    }
+}
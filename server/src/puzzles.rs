@@ -0,0 +1,198 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use nerdle_ui::{
+    api::Wordle,
+    state::{content_hash, Language, Wordlist},
+};
+
+use crate::{
+    error::{ApiError, ApiResult},
+    AppState,
+};
+
+/// Puzzles and word lists, read from a data directory
+/// laid out like the `data/` directory of the ui:
+/// english files at the top, other languages in
+/// a subdirectory named by their code.
+pub struct PuzzleStore {
+    data_dir: PathBuf,
+}
+
+impl PuzzleStore {
+    pub fn new(data_dir: PathBuf) -> Self {
+        Self { data_dir }
+    }
+
+    fn language_dir(&self, language: &Language) -> PathBuf {
+        match language {
+            Language::English => self.data_dir.clone(),
+            _ => self.data_dir.join(language.code()),
+        }
+    }
+
+    pub async fn by_date(
+        &self,
+        language: &Language,
+        date: NaiveDate,
+    ) -> Result<Option<Wordle>> {
+        let path = self
+            .language_dir(language)
+            .join(format!("{}.json", date.format("%Y-%m-%d")));
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = tokio::fs::read_to_string(path).await?;
+        Ok(Some(serde_json::from_str(&text)?))
+    }
+
+    pub async fn by_id(
+        &self,
+        language: &Language,
+        id: u32,
+    ) -> Result<Option<Wordle>> {
+        let mut entries =
+            tokio::fs::read_dir(self.language_dir(language)).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let text = tokio::fs::read_to_string(path).await?;
+            let wordle: Wordle = serde_json::from_str(&text)?;
+            if wordle.id == id {
+                return Ok(Some(wordle));
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn wordlist(&self, language: &Language) -> Result<String> {
+        let path = self.language_dir(language).join("words.txt");
+        Ok(tokio::fs::read_to_string(path).await?)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct LanguageQuery {
+    pub lang: Option<String>,
+}
+
+impl LanguageQuery {
+    pub fn language(&self) -> ApiResult<Language> {
+        match &self.lang {
+            None => Ok(Language::default()),
+            Some(code) => Language::from_code(code).ok_or_else(|| {
+                ApiError::BadRequest(format!("unknown language: {}", code))
+            }),
+        }
+    }
+}
+
+impl AppState {
+    fn publish(&self, wordle: Option<Wordle>) -> ApiResult<Json<Wordle>> {
+        let mut wordle = wordle.ok_or(ApiError::NotFound)?;
+        if !self.expose_solutions {
+            wordle.solution.clear();
+        }
+        Ok(Json(wordle))
+    }
+}
+
+#[derive(Serialize)]
+pub struct WordlistInfo {
+    pub language: String,
+    pub version: String,
+    pub words: usize,
+}
+
+async fn today(
+    State(state): State<AppState>,
+    Query(query): Query<LanguageQuery>,
+) -> ApiResult<Json<Wordle>> {
+    let date = Utc::now().date_naive();
+    let wordle = state.puzzles.by_date(&query.language()?, date).await?;
+    state.publish(wordle)
+}
+
+async fn by_date(
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+    Query(query): Query<LanguageQuery>,
+) -> ApiResult<Json<Wordle>> {
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|_| ApiError::BadRequest(format!("invalid date: {}", date)))?;
+    let wordle = state.puzzles.by_date(&query.language()?, date).await?;
+    state.publish(wordle)
+}
+
+async fn by_id(
+    State(state): State<AppState>,
+    Path(id): Path<u32>,
+    Query(query): Query<LanguageQuery>,
+) -> ApiResult<Json<Wordle>> {
+    let wordle = state.puzzles.by_id(&query.language()?, id).await?;
+    state.publish(wordle)
+}
+
+async fn wordlists(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<WordlistInfo>>> {
+    let mut infos = vec![];
+    for language in Language::all() {
+        let text = state.puzzles.wordlist(&language).await?;
+        infos.push(WordlistInfo {
+            language: language.code(),
+            version: content_hash(&text),
+            words: Wordlist::from(text).len(),
+        });
+    }
+    Ok(Json(infos))
+}
+
+async fn wordlist(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let language = Language::from_code(&code).ok_or(ApiError::NotFound)?;
+    let text = state.puzzles.wordlist(&language).await?;
+    let version = content_hash(&text);
+    let etag = format!("\"{}\"", version);
+    let cached = headers
+        .get(header::IF_NONE_MATCH)
+        .is_some_and(|value| value.as_bytes() == etag.as_bytes());
+    if cached {
+        return Ok(StatusCode::NOT_MODIFIED.into_response());
+    }
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/plain; charset=utf-8".to_string(),
+            ),
+            (header::ETAG, etag),
+        ],
+        text,
+    )
+        .into_response())
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/puzzles/today", get(today))
+        .route("/puzzles/date/:date", get(by_date))
+        .route("/puzzles/id/:id", get(by_id))
+        .route("/wordlists", get(wordlists))
+        .route("/wordlists/:lang", get(wordlist))
+}
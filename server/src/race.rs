@@ -0,0 +1,452 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
+    response::Response,
+    routing::{get, post},
+    Json, Router,
+};
+use futures::{SinkExt, StreamExt};
+use rand::{seq::SliceRandom, Rng};
+use serde::Deserialize;
+use tokio::sync::broadcast;
+
+use nerdle_ui::{
+    api::clean_name,
+    state::{
+        game::State as GameState, ClientMessage, Language, Player, RoomInfo,
+        ServerMessage, Wordlist,
+    },
+};
+
+use crate::{
+    error::{ApiError, ApiResult},
+    puzzles::LanguageQuery,
+    AppState,
+};
+
+/// Letters of room codes, without the easily confused ones
+const CODE_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const CODE_LENGTH: usize = 5;
+
+/// Number of messages a slow player may fall behind
+const CHANNEL_SIZE: usize = 64;
+
+/// Most rooms open at once
+const MAX_ROOMS: usize = 1000;
+
+/// Time to join a new room before it is closed
+const JOIN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+const MAX_TRIES: usize = 6;
+
+/// A race: players guessing the same solution
+struct Room {
+    language: Language,
+    solution: String,
+    created: Instant,
+    players: Mutex<Vec<Player>>,
+    next_id: Mutex<u32>,
+    events: broadcast::Sender<ServerMessage>,
+}
+
+/// Open race rooms. A room is closed when its last
+/// player leaves, or if nobody joins it in time.
+#[derive(Default)]
+pub struct RoomStore {
+    rooms: Mutex<HashMap<String, Arc<Room>>>,
+}
+
+impl RoomStore {
+    fn create(
+        &self,
+        language: Language,
+        solution: String,
+    ) -> ApiResult<String> {
+        self.create_at(language, solution, Instant::now())
+    }
+
+    fn create_at(
+        &self,
+        language: Language,
+        solution: String,
+        now: Instant,
+    ) -> ApiResult<String> {
+        let mut rooms = self.rooms.lock().unwrap();
+        rooms.retain(|_, room| !room.expired(now));
+        if rooms.len() >= MAX_ROOMS {
+            return Err(ApiError::Unavailable("too many rooms".into()));
+        }
+        let mut rng = rand::thread_rng();
+        let code = loop {
+            let code: String = (0..CODE_LENGTH)
+                .map(|_| {
+                    CODE_LETTERS[rng.gen_range(0..CODE_LETTERS.len())] as char
+                })
+                .collect();
+            if !rooms.contains_key(&code) {
+                break code;
+            }
+        };
+        let (events, _) = broadcast::channel(CHANNEL_SIZE);
+        let room = Room {
+            language,
+            solution,
+            created: now,
+            players: Mutex::default(),
+            next_id: Mutex::new(1),
+            events,
+        };
+        rooms.insert(code.clone(), Arc::new(room));
+        Ok(code)
+    }
+
+    fn get(&self, code: &str) -> Option<Arc<Room>> {
+        let rooms = self.rooms.lock().unwrap();
+        let room = rooms.get(code)?;
+        (!room.expired(Instant::now())).then(|| room.clone())
+    }
+
+    fn close_if_empty(&self, code: &str) {
+        let mut rooms = self.rooms.lock().unwrap();
+        let empty = rooms
+            .get(code)
+            .is_some_and(|room| room.players.lock().unwrap().is_empty());
+        if empty {
+            rooms.remove(code);
+        }
+    }
+}
+
+impl Room {
+    /// Nobody joined the room in time
+    fn expired(&self, now: Instant) -> bool {
+        *self.next_id.lock().unwrap() == 1
+            && now.duration_since(self.created) > JOIN_TIMEOUT
+    }
+
+    fn join(&self, name: String) -> (Player, ServerMessage) {
+        let mut next_id = self.next_id.lock().unwrap();
+        let player = Player {
+            id: *next_id,
+            name,
+            hints: vec![],
+            state: GameState::Running,
+        };
+        *next_id += 1;
+        let mut players = self.players.lock().unwrap();
+        players.push(player.clone());
+        let welcome = ServerMessage::Welcome {
+            player: player.id,
+            solution: self.solution.clone(),
+            language: self.language.clone(),
+            players: players.clone(),
+        };
+        (player, welcome)
+    }
+
+    /// Store the board of a player and send it to the room.
+    /// Boards which can't belong to the solution are rejected.
+    fn update(&self, id: u32, message: ClientMessage) -> Result<(), String> {
+        let length = self.solution.chars().count();
+        let mut players = self.players.lock().unwrap();
+        let Some(player) = players.iter_mut().find(|p| p.id == id) else {
+            return Ok(());
+        };
+        match message {
+            ClientMessage::Board { hints, state } => {
+                if hints.len() > MAX_TRIES {
+                    return Err(format!("{} rows", hints.len()));
+                }
+                if let Some(row) = hints.iter().find(|row| row.len() > length) {
+                    return Err(format!("row of {} letters", row.len()));
+                }
+                player.hints = hints;
+                player.state = state;
+            }
+        }
+        let _ = self.events.send(ServerMessage::Board {
+            player: player.clone(),
+        });
+        Ok(())
+    }
+
+    fn leave(&self, id: u32) {
+        self.players.lock().unwrap().retain(|p| p.id != id);
+        let _ = self.events.send(ServerMessage::Left { player: id });
+    }
+}
+
+async fn create(
+    State(state): State<AppState>,
+    Query(query): Query<LanguageQuery>,
+) -> ApiResult<Json<RoomInfo>> {
+    let language = query.language()?;
+    let wordlist = Wordlist::from(state.puzzles.wordlist(&language).await?);
+    let solution = wordlist
+        .words()
+        .choose(&mut rand::thread_rng())
+        .cloned()
+        .ok_or(ApiError::NotFound)?;
+    let code = state.rooms.create(language, solution)?;
+    Ok(Json(RoomInfo { code }))
+}
+
+#[derive(Deserialize)]
+struct JoinQuery {
+    name: Option<String>,
+}
+
+async fn join(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+    Query(query): Query<JoinQuery>,
+    upgrade: WebSocketUpgrade,
+) -> ApiResult<Response> {
+    let code = code.to_uppercase();
+    let room = state.rooms.get(&code).ok_or(ApiError::NotFound)?;
+    let name = clean_name(&query.name.unwrap_or_default());
+    let name = match name.is_empty() {
+        true => "?".into(),
+        false => name,
+    };
+    Ok(upgrade.on_upgrade(move |socket| async move {
+        play(socket, &room, name).await;
+        state.rooms.close_if_empty(&code);
+    }))
+}
+
+fn encode(message: &ServerMessage) -> Message {
+    Message::Text(serde_json::to_string(message).unwrap())
+}
+
+/// Relay the boards between a player and the room until
+/// the player leaves
+async fn play(socket: WebSocket, room: &Room, name: String) {
+    let (mut write, mut read) = socket.split();
+    let mut events = room.events.subscribe();
+    let (player, welcome) = room.join(name);
+    let _ = room.events.send(ServerMessage::Board {
+        player: player.clone(),
+    });
+    if write.send(encode(&welcome)).await.is_err() {
+        room.leave(player.id);
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            message = read.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+                let result = serde_json::from_str::<ClientMessage>(&text)
+                    .map_err(|err| err.to_string())
+                    .and_then(|message| room.update(player.id, message));
+                if let Err(err) = result {
+                    eprintln!("invalid race message: {}", err);
+                }
+            }
+            event = events.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                if write.send(encode(&event)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    room.leave(player.id);
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/race", post(create))
+        .route("/race/:code", get(join))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nerdle_ui::state::Hint;
+    use tokio_tungstenite::{connect_async, tungstenite, MaybeTlsStream};
+
+    use crate::relay;
+
+    type Socket = tokio_tungstenite::WebSocketStream<
+        MaybeTlsStream<tokio::net::TcpStream>,
+    >;
+
+    fn board(rows: usize, letters: usize) -> ClientMessage {
+        ClientMessage::Board {
+            hints: vec![vec![Hint::Incorrect; letters]; rows],
+            state: GameState::Running,
+        }
+    }
+
+    #[test]
+    fn closes_rooms_nobody_joins_in_time() {
+        let rooms = RoomStore::default();
+        let now = Instant::now();
+        let joined = rooms.create_at(Language::English, "CRANE".into(), now);
+        let joined = joined.unwrap();
+        let idle = rooms.create_at(Language::English, "CRANE".into(), now);
+        let idle = idle.unwrap();
+        rooms.get(&joined).unwrap().join("alice".into());
+
+        let later = now + JOIN_TIMEOUT + Duration::from_secs(1);
+        rooms
+            .create_at(Language::English, "CRANE".into(), later)
+            .unwrap();
+        let open = rooms.rooms.lock().unwrap();
+        assert!(open.contains_key(&joined));
+        assert!(!open.contains_key(&idle));
+        assert_eq!(open.len(), 2);
+    }
+
+    #[test]
+    fn limits_the_number_of_rooms() {
+        let rooms = RoomStore::default();
+        for _ in 0..MAX_ROOMS {
+            rooms.create(Language::English, "CRANE".into()).unwrap();
+        }
+        let err = rooms.create(Language::English, "CRANE".into());
+        assert!(matches!(err, Err(ApiError::Unavailable(_))));
+    }
+
+    #[test]
+    fn rejects_boards_which_do_not_fit_the_solution() {
+        let rooms = RoomStore::default();
+        let code = rooms.create(Language::English, "CRANE".into()).unwrap();
+        let room = rooms.get(&code).unwrap();
+        let mut events = room.events.subscribe();
+        let (player, _) = room.join("alice".into());
+
+        assert!(room.update(player.id, board(7, 5)).is_err());
+        assert!(room.update(player.id, board(1, 6)).is_err());
+        assert!(events.try_recv().is_err());
+
+        room.update(player.id, board(6, 5)).unwrap();
+        let ServerMessage::Board { player } = events.try_recv().unwrap() else {
+            panic!("expected a board");
+        };
+        assert_eq!(player.hints.len(), 6);
+    }
+
+    async fn connect(address: std::net::SocketAddr, path: &str) -> Socket {
+        let url = format!("ws://{}/api/v1/race/{}", address, path);
+        connect_async(url).await.unwrap().0
+    }
+
+    async fn receive(socket: &mut Socket) -> ServerMessage {
+        loop {
+            let message =
+                tokio::time::timeout(Duration::from_secs(5), socket.next())
+                    .await
+                    .expect("no message from the relay");
+            if let tungstenite::Message::Text(text) = message.unwrap().unwrap()
+            {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    async fn send(socket: &mut Socket, message: &ClientMessage) {
+        let text = serde_json::to_string(message).unwrap();
+        socket.send(tungstenite::Message::Text(text)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn relays_boards_between_the_players_of_a_room() {
+        let state = AppState::new("../ui/data".into());
+        let code = state
+            .rooms
+            .create(Language::English, "CRANE".into())
+            .unwrap();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await;
+        let listener = listener.unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = relay(state.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        assert!(matches!(
+            connect_async(format!("ws://{}/api/v1/race/NOPE", address)).await,
+            Err(tungstenite::Error::Http(_))
+        ));
+
+        // Joining players are welcomed with the room
+        let mut alice = connect(address, &format!("{}?name=alice", code)).await;
+        let ServerMessage::Welcome {
+            player: alice_id,
+            solution,
+            players,
+            ..
+        } = receive(&mut alice).await
+        else {
+            panic!("expected a welcome");
+        };
+        assert_eq!(solution, "CRANE");
+        assert_eq!(players.len(), 1);
+        assert!(matches!(
+            receive(&mut alice).await,
+            ServerMessage::Board { .. }
+        ));
+
+        let path = format!("{}?name=bob", code.to_lowercase());
+        let mut bob = connect(address, &path).await;
+        let ServerMessage::Welcome {
+            player: bob_id,
+            players,
+            ..
+        } = receive(&mut bob).await
+        else {
+            panic!("expected a welcome");
+        };
+        let names: Vec<&str> =
+            players.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["alice", "bob"]);
+        let ServerMessage::Board { player } = receive(&mut alice).await else {
+            panic!("expected the board of bob");
+        };
+        assert_eq!(player.id, bob_id);
+        receive(&mut bob).await;
+
+        // Boards go to every player, broken ones to nobody
+        send(&mut alice, &board(7, 5)).await;
+        send(&mut alice, &board(2, 5)).await;
+        for socket in [&mut alice, &mut bob] {
+            let ServerMessage::Board { player } = receive(socket).await else {
+                panic!("expected the board of alice");
+            };
+            assert_eq!((player.id, player.hints.len()), (alice_id, 2));
+        }
+
+        // Leaving is announced, and the last one closes the room
+        bob.close(None).await.unwrap();
+        assert_eq!(
+            receive(&mut alice).await,
+            ServerMessage::Left { player: bob_id }
+        );
+        alice.close(None).await.unwrap();
+        for _ in 0..50 {
+            if state.rooms.get(&code).is_none() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("the room was not closed");
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::Mutex;
+
+use axum::{
+    body::Bytes, extract::State, http::StatusCode, routing::post, Json, Router,
+};
+
+use nerdle_ui::debug::ErrorReport;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    AppState,
+};
+
+/// Number of reports kept for inspection
+const MAX_REPORTS: usize = 100;
+
+/// A stub sink for the error reports of the ui: reports are
+/// printed and the latest ones kept in memory.
+#[derive(Default)]
+pub struct ReportStore {
+    reports: Mutex<Vec<ErrorReport>>,
+}
+
+impl ReportStore {
+    pub fn add(&self, report: ErrorReport) {
+        let mut reports = self.reports.lock().unwrap();
+        if reports.len() >= MAX_REPORTS {
+            reports.remove(0);
+        }
+        reports.push(report);
+    }
+
+    pub fn all(&self) -> Vec<ErrorReport> {
+        self.reports.lock().unwrap().clone()
+    }
+}
+
+/// Reports are sent as beacons with a text content type,
+/// so the body is parsed regardless of the headers.
+async fn add(
+    State(state): State<AppState>,
+    body: Bytes,
+) -> ApiResult<StatusCode> {
+    let report: ErrorReport = serde_json::from_slice(&body)
+        .map_err(|err| ApiError::BadRequest(err.to_string()))?;
+    eprintln!(
+        "[{}] {} {}: {}",
+        report.timestamp, report.level, report.url, report.message
+    );
+    state.reports.add(report);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list(State(state): State<AppState>) -> Json<Vec<ErrorReport>> {
+    Json(state.reports.all())
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/reports", post(add).get(list))
+}
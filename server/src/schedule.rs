@@ -0,0 +1,262 @@
+//! Generate a schedule of daily puzzles from a list of answers.
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Result};
+use chrono::{Duration, NaiveDate};
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use nerdle_ui::{
+    api::Wordle,
+    state::{normalize, Wordlist},
+};
+
+pub struct ScheduleOptions {
+    /// Date of the first generated puzzle
+    pub start: NaiveDate,
+    /// Number of puzzles to generate
+    pub days: usize,
+    /// Seed of the shuffle, the same seed gives the same schedule
+    pub seed: u64,
+    pub editor: String,
+}
+
+/// All puzzles of a data directory, ordered by date
+fn read_puzzles(dir: &Path) -> Result<Vec<Wordle>> {
+    let mut puzzles = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let text = std::fs::read_to_string(&path)?;
+        puzzles.push(serde_json::from_str(&text)?);
+    }
+    puzzles.sort_by(|a: &Wordle, b: &Wordle| a.print_date.cmp(&b.print_date));
+    Ok(puzzles)
+}
+
+/// Ids continue from the latest existing puzzle before the
+/// start date, one per day. Without one, the schedule starts
+/// at id 1 on its first day.
+fn first_numbers(existing: &[Wordle], start: NaiveDate) -> (u32, usize) {
+    let latest = existing.iter().rev().find_map(|wordle| {
+        let date =
+            NaiveDate::parse_from_str(&wordle.print_date, "%Y-%m-%d").ok()?;
+        (date < start).then_some((wordle, date))
+    });
+    match latest {
+        Some((wordle, date)) => {
+            let days = (start - date).num_days();
+            (
+                wordle.id + days as u32,
+                wordle.days_since_launch + days as usize,
+            )
+        }
+        None => (1, 0),
+    }
+}
+
+/// Build the puzzles of the schedule. Every answer must be
+/// in the word list, and answers which were already used by
+/// a puzzle in `existing` or appear twice are never repeated.
+/// Fails if an id is taken by a puzzle after the schedule.
+pub fn generate(
+    answers: &str,
+    wordlist: &Wordlist,
+    existing: &[Wordle],
+    options: &ScheduleOptions,
+) -> Result<Vec<Wordle>> {
+    let unknown: Vec<String> = answers
+        .lines()
+        .map(normalize)
+        .filter(|answer| !answer.is_empty() && !wordlist.contains(answer))
+        .collect();
+    if !unknown.is_empty() {
+        bail!("answers not in the word list: {}", unknown.join(", "));
+    }
+
+    let mut used: HashSet<String> = existing
+        .iter()
+        .map(|wordle| normalize(&wordle.solution))
+        .collect();
+    let mut candidates: Vec<String> = answers
+        .lines()
+        .map(normalize)
+        .filter(|answer| !answer.is_empty() && used.insert(answer.clone()))
+        .collect();
+    if candidates.len() < options.days {
+        bail!(
+            "{} unused answers left, {} needed",
+            candidates.len(),
+            options.days
+        );
+    }
+
+    // Sort first, so the schedule only depends on the seed
+    // and the set of answers, not on their order.
+    candidates.sort();
+    let mut rng = ChaCha8Rng::seed_from_u64(options.seed);
+    candidates.shuffle(&mut rng);
+
+    let (first_id, first_day) = first_numbers(existing, options.start);
+    let puzzles: Vec<Wordle> = candidates
+        .into_iter()
+        .take(options.days)
+        .enumerate()
+        .map(|(i, solution)| Wordle {
+            days_since_launch: first_day + i,
+            editor: options.editor.clone(),
+            id: first_id + i as u32,
+            print_date: (options.start + Duration::days(i as i64))
+                .format("%Y-%m-%d")
+                .to_string(),
+            solution: solution.to_lowercase(),
+        })
+        .collect();
+    for wordle in &puzzles {
+        if let Some(taken) = existing.iter().find(|e| e.id == wordle.id) {
+            bail!(
+                "id {} of {} is already used on {}",
+                wordle.id,
+                wordle.print_date,
+                taken.print_date
+            );
+        }
+    }
+    Ok(puzzles)
+}
+
+/// Generate a schedule into a data directory, next to its
+/// `words.txt`. Existing puzzles are never overwritten.
+pub fn write_schedule(
+    data_dir: &Path,
+    answers: &str,
+    options: &ScheduleOptions,
+) -> Result<Vec<PathBuf>> {
+    let words = std::fs::read_to_string(data_dir.join("words.txt"))?;
+    let wordlist = Wordlist::from(words);
+    let existing = read_puzzles(data_dir)?;
+    let puzzles = generate(answers, &wordlist, &existing, options)?;
+
+    let paths: Vec<PathBuf> = puzzles
+        .iter()
+        .map(|wordle| data_dir.join(format!("{}.json", wordle.print_date)))
+        .collect();
+    if let Some(path) = paths.iter().find(|path| path.exists()) {
+        bail!("puzzle already exists: {}", path.display());
+    }
+    for (wordle, path) in puzzles.iter().zip(&paths) {
+        std::fs::write(path, serde_json::to_string(wordle)?)?;
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "crane\nslate\nplumb\ngusto\nwharf\n";
+
+    fn wordlist() -> Wordlist {
+        Wordlist::from(format!("{}adieu\n", ANSWERS))
+    }
+
+    fn options(seed: u64, days: usize) -> ScheduleOptions {
+        ScheduleOptions {
+            start: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+            days,
+            seed,
+            editor: "editor".into(),
+        }
+    }
+
+    fn solutions(puzzles: &[Wordle]) -> Vec<String> {
+        puzzles
+            .iter()
+            .map(|wordle| wordle.solution.clone())
+            .collect()
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_schedule() {
+        let wordlist = wordlist();
+        let first = generate(ANSWERS, &wordlist, &[], &options(7, 5));
+        let shuffled = "wharf\ngusto\ncrane\nplumb\nslate\n";
+        let second = generate(shuffled, &wordlist, &[], &options(7, 5));
+        assert_eq!(first.unwrap(), second.unwrap());
+    }
+
+    #[test]
+    fn numbers_the_days_after_the_existing_puzzles() {
+        let existing = [Wordle {
+            days_since_launch: 10,
+            editor: "editor".into(),
+            id: 100,
+            print_date: "2024-04-29".into(),
+            solution: "adieu".into(),
+        }];
+        let puzzles =
+            generate(ANSWERS, &wordlist(), &existing, &options(1, 2)).unwrap();
+        assert_eq!(puzzles[0].id, 102);
+        assert_eq!(puzzles[0].days_since_launch, 12);
+        assert_eq!(puzzles[0].print_date, "2024-05-01");
+        assert_eq!(puzzles[1].id, 103);
+        assert_eq!(puzzles[1].print_date, "2024-05-02");
+    }
+
+    #[test]
+    fn fails_on_ids_of_later_puzzles() {
+        let existing = [
+            Wordle {
+                id: 100,
+                print_date: "2024-04-30".into(),
+                solution: "adieu".into(),
+                ..Wordle::default()
+            },
+            Wordle {
+                id: 103,
+                print_date: "2024-05-10".into(),
+                solution: "slate".into(),
+                ..Wordle::default()
+            },
+        ];
+        let wordlist = wordlist();
+        assert!(generate(ANSWERS, &wordlist, &existing, &options(1, 2)).is_ok());
+        let err = generate(ANSWERS, &wordlist, &existing, &options(1, 3));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "id 103 of 2024-05-03 is already used on 2024-05-10"
+        );
+    }
+
+    #[test]
+    fn never_repeats_an_answer() {
+        let existing = [Wordle {
+            print_date: "2024-04-30".into(),
+            solution: "crane".into(),
+            ..Wordle::default()
+        }];
+        let answers = format!("{}slate\n", ANSWERS);
+        let puzzles =
+            generate(&answers, &wordlist(), &existing, &options(3, 4)).unwrap();
+        let mut solutions = solutions(&puzzles);
+        solutions.sort();
+        assert_eq!(solutions, ["gusto", "plumb", "slate", "wharf"]);
+
+        let err = generate(&answers, &wordlist(), &existing, &options(3, 5));
+        assert!(err.unwrap_err().to_string().contains("4 unused answers"));
+    }
+
+    #[test]
+    fn rejects_answers_outside_the_word_list() {
+        let answers = format!("{}zzzzz\nqqqqq\n", ANSWERS);
+        let err = generate(&answers, &wordlist(), &[], &options(1, 1));
+        let message = err.unwrap_err().to_string();
+        assert!(message.contains("ZZZZZ"), "{}", message);
+        assert!(message.contains("QQQQQ"), "{}", message);
+    }
+}
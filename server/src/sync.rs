@@ -0,0 +1,173 @@
+use std::{path::Path as FilePath, sync::Mutex};
+
+use anyhow::Result;
+use axum::{
+    extract::{Path, State},
+    routing::post,
+    Json, Router,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use nerdle_ui::{
+    api::clean_name,
+    state::{merge, Game, SyncRequest, SyncResponse, MIN_SYNC_CODE_LENGTH},
+};
+
+use crate::{
+    error::{ApiError, ApiResult},
+    AppState,
+};
+
+/// Most games pushed at once
+const MAX_GAMES: usize = 5000;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS games (
+    code TEXT NOT NULL,
+    key TEXT NOT NULL,
+    game TEXT NOT NULL,
+    PRIMARY KEY (code, key)
+);
+";
+
+/// Games of every sync code, in SQLite
+pub struct SyncStore {
+    db: Mutex<Connection>,
+}
+
+impl SyncStore {
+    pub fn open(path: &FilePath) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// A store which is gone with the server
+    pub fn in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(db: Connection) -> Result<Self> {
+        db.execute_batch(SCHEMA)?;
+        Ok(Self { db: Mutex::new(db) })
+    }
+
+    /// Merge games into the stored ones of the code and
+    /// return all games of the code.
+    pub fn sync(&self, code: &str, games: &[Game]) -> Result<Vec<Game>> {
+        let mut db = self.db.lock().unwrap();
+        let tx = db.transaction()?;
+        for game in games {
+            let key = game.storage_key();
+            let stored: Option<String> = tx
+                .query_row(
+                    "SELECT game FROM games WHERE code = ?1 AND key = ?2",
+                    params![code, key],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let merged = match stored {
+                Some(stored) => merge(&serde_json::from_str(&stored)?, game),
+                None => game.clone(),
+            };
+            tx.execute(
+                "INSERT OR REPLACE INTO games (code, key, game)
+                 VALUES (?1, ?2, ?3)",
+                params![code, key, serde_json::to_string(&merged)?],
+            )?;
+        }
+        let games = {
+            let mut query =
+                tx.prepare("SELECT game FROM games WHERE code = ?1")?;
+            let rows = query
+                .query_map(params![code], |row| row.get::<_, String>(0))?;
+            rows.map(|row| Ok(serde_json::from_str(&row?)?))
+                .collect::<Result<Vec<Game>>>()?
+        };
+        tx.commit()?;
+        Ok(games)
+    }
+}
+
+async fn sync(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+    Json(request): Json<SyncRequest>,
+) -> ApiResult<Json<SyncResponse>> {
+    if clean_name(&code) != code || code.len() < MIN_SYNC_CODE_LENGTH {
+        return Err(ApiError::BadRequest("invalid sync code".into()));
+    }
+    if request.games.len() > MAX_GAMES {
+        return Err(ApiError::BadRequest("too many games".into()));
+    }
+    // Games with id 0 are never stored, and neither synced
+    let games: Vec<Game> = request
+        .games
+        .into_iter()
+        .filter(|game| game.id != 0)
+        .collect();
+    let games = state.sync.sync(&code, &games)?;
+    Ok(Json(SyncResponse { games }))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/sync/:code", post(sync))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::slice;
+
+    use super::*;
+
+    use nerdle_ui::state::{game::State, GameEvent, GameEventKind, Guess};
+
+    /// A game with a guess per word, changed last at `at`
+    fn game(id: u32, words: &[&str], at: i64) -> Game {
+        let guesses: Vec<Guess> = words.iter().map(|&w| w.into()).collect();
+        let events = guesses
+            .iter()
+            .map(|guess| GameEvent {
+                at,
+                kind: GameEventKind::Submitted(guess.clone()),
+            })
+            .collect();
+        let state = match words.last() {
+            Some(&"BROTH") => State::Win,
+            _ => State::Running,
+        };
+        Game {
+            id,
+            solution: "BROTH".into(),
+            guesses,
+            events,
+            state,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn keeps_completed_games_over_later_running_ones() {
+        let store = SyncStore::in_memory().unwrap();
+        let finished = game(1, &["CRANE", "BROTH"], 10);
+        store.sync("device00", slice::from_ref(&finished)).unwrap();
+
+        let running = game(1, &["PLUMB"], 20);
+        let games = store.sync("device00", &[running]).unwrap();
+        assert_eq!(games, slice::from_ref(&finished));
+        assert_eq!(store.sync("device00", &[]).unwrap(), [finished]);
+    }
+
+    #[test]
+    fn keeps_the_games_of_every_code_apart() {
+        let store = SyncStore::in_memory().unwrap();
+        let first = game(1, &["CRANE"], 10);
+        let second = game(1, &["PLUMB", "CRANE"], 20);
+        store.sync("phone000", slice::from_ref(&first)).unwrap();
+        store.sync("laptop00", slice::from_ref(&second)).unwrap();
+        store.sync("laptop00", &[game(2, &[], 0)]).unwrap();
+
+        assert_eq!(store.sync("phone000", &[]).unwrap(), [first]);
+        let games = store.sync("laptop00", &[]).unwrap();
+        assert_eq!(games.len(), 2);
+        assert!(games.contains(&second));
+    }
+}
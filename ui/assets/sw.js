@@ -0,0 +1,140 @@
+// Service worker of nerdle: keeps the app and the upcoming
+// puzzles for offline play.
+//
+// `__BUILD__` is replaced by the name of the wasm bundle after
+// every build (see `Trunk.toml`), so a new build installs a new
+// worker. The app offers to reload once it is waiting.
+
+const BUILD = "__BUILD__";
+const CACHE = `nerdle-${BUILD}`;
+
+// Directories of the day files of every language, passed by the
+// app as `sw.js?data=data,data/de`. None in server mode, which
+// keeps the solutions off the client.
+const DATA_DIRS = (new URL(self.location).searchParams.get("data") || "")
+  .split(",")
+  .filter((dir) => dir);
+
+// Puzzles fetched ahead, including today's
+const DAYS_AHEAD = 7;
+
+const SHELL = [
+  "./",
+  "manifest.webmanifest",
+  "icons/icon.svg",
+  "icons/icon-192.png",
+  "icons/icon-512.png",
+  "icons/apple-touch-icon.png",
+];
+
+/// `YYYY-MM-DD` of a day from today, in UTC like the app
+function day(offset) {
+  const date = new Date(Date.now() + offset * 24 * 60 * 60 * 1000);
+  return date.toISOString().slice(0, 10);
+}
+
+function dataFiles() {
+  const files = [];
+  for (const dir of DATA_DIRS) {
+    for (let offset = 0; offset < DAYS_AHEAD; offset++) {
+      files.push(`${dir}/${day(offset)}.json`);
+    }
+  }
+  return files;
+}
+
+/// The scripts, wasm and styles linked by the page, whose
+/// names change with every build
+async function bundleFiles() {
+  const response = await fetch("./", { cache: "no-cache" });
+  const html = await response.text();
+  const links = html.matchAll(/(?:href|src)="([^"]+\.(?:js|wasm|css))"/g);
+  return Array.from(links, (match) => match[1]);
+}
+
+/// Cache what can be fetched; puzzles which are not
+/// published yet are skipped.
+async function precache(cache, urls) {
+  await Promise.allSettled(urls.map((url) => cache.add(url)));
+}
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    (async () => {
+      const cache = await caches.open(CACHE);
+      await cache.addAll(SHELL);
+      await precache(cache, await bundleFiles());
+      await precache(cache, dataFiles());
+    })(),
+  );
+});
+
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    (async () => {
+      const names = await caches.keys();
+      await Promise.all(
+        names
+          .filter((name) => name.startsWith("nerdle-") && name !== CACHE)
+          .map((name) => caches.delete(name)),
+      );
+      await self.clients.claim();
+    })(),
+  );
+});
+
+// Sent by the app when the player accepts the update
+self.addEventListener("message", (event) => {
+  if (event.data === "skip-waiting") {
+    self.skipWaiting();
+  }
+});
+
+/// Network first, so puzzles and word lists stay fresh
+async function networkFirst(request, fallback) {
+  const cache = await caches.open(CACHE);
+  try {
+    const response = await fetch(request);
+    if (response.ok) {
+      cache.put(request, response.clone());
+    }
+    return response;
+  } catch (error) {
+    const cached = await cache.match(fallback || request);
+    if (cached) {
+      return cached;
+    }
+    throw error;
+  }
+}
+
+/// Cache first for the files of a build, which never change
+async function cacheFirst(request) {
+  const cache = await caches.open(CACHE);
+  const cached = await cache.match(request);
+  if (cached) {
+    return cached;
+  }
+  const response = await fetch(request);
+  if (response.ok) {
+    cache.put(request, response.clone());
+  }
+  return response;
+}
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  const url = new URL(request.url);
+  // The API of server mode is left to the network
+  if (request.method !== "GET" || url.origin !== self.location.origin) {
+    return;
+  }
+  if (request.mode === "navigate") {
+    // Every route is served by the page of the app
+    event.respondWith(networkFirst(request, "./"));
+  } else if (url.pathname.includes("/data/")) {
+    event.respondWith(networkFirst(request));
+  } else {
+    event.respondWith(cacheFirst(request));
+  }
+});
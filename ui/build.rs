@@ -0,0 +1,32 @@
+//! Embed the word lists of all languages, compactly
+//! encoded, along with the hash of their text.
+use std::{env, fs, path::Path};
+
+// Only the encoder is needed here
+#[allow(dead_code)]
+#[path = "src/encoding.rs"]
+mod encoding;
+
+/// Language code and the directory of its word list
+const WORDLISTS: [(&str, &str); 3] =
+    [("en", "data"), ("de", "data/de"), ("es", "data/es")];
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    for (code, dir) in WORDLISTS {
+        let path = Path::new(dir).join("words.txt");
+        println!("cargo:rerun-if-changed={}", path.display());
+        let text = fs::read_to_string(&path).unwrap();
+        let out = Path::new(&out_dir);
+        fs::write(
+            out.join(format!("words-{}.bin", code)),
+            encoding::encode(&text),
+        )
+        .unwrap();
+        fs::write(
+            out.join(format!("words-{}.hash", code)),
+            encoding::content_hash(&text),
+        )
+        .unwrap();
+    }
+}
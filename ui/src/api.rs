@@ -1,29 +1,368 @@
-use anyhow::Result;
-use gloo::net::http::Request;
-use serde::Deserialize;
+use std::fmt;
+
+use chrono::{NaiveDate, Utc};
+
+use gloo::{
+    net::http::{Method, Request, RequestBuilder, Response},
+    timers::future::TimeoutFuture,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use web_sys::RequestMode;
 
-use crate::state::Wordlist;
+use crate::state::{
+    Game, Guess, Hint, Language, RoomInfo, SyncRequest, SyncResponse, Wordlist,
+};
+
+/// Base URL of the puzzle API, e.g. `http://localhost:8081`,
+/// set at build time. Without it, the static files
+/// bundled from `data/` are used.
+pub const API_URL: Option<&str> = option_env!("NERDLE_API_URL");
+
+/// Path the app is hosted under, e.g. `/nerdle`, set at
+/// build time. The static files are loaded below it.
+pub const BASE_PATH: Option<&str> = option_env!("NERDLE_BASE_PATH");
+
+/// URL of a relay for race rooms, e.g. `http://localhost:8082`,
+/// set at build time. Defaults to the puzzle API.
+pub const RELAY_URL: Option<&str> = option_env!("NERDLE_RELAY_URL");
+
+/// Guesses are scored by the API and the solution is never
+/// downloaded before the game is over.
+pub fn server_mode() -> bool {
+    API_URL.is_some()
+}
+
+/// Number of times a GET request is retried after a network
+/// error or a server error
+const RETRIES: u32 = 3;
+
+/// Delay before the first retry in milliseconds,
+/// doubled with every further retry
+const RETRY_DELAY: u32 = 250;
+
+/// Longest name of a player or group
+pub const MAX_NAME_LENGTH: usize = 16;
+
+/// Names of players and groups are short and alphanumeric,
+/// so they fit into paths and queries as they are.
+pub fn clean_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .take(MAX_NAME_LENGTH)
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    /// The request did not get a response
+    Network(String),
+    /// The response has an unexpected status code
+    Status(u16),
+    /// The response body could not be read
+    Parse(String),
+}
+
+impl ApiError {
+    /// Worth trying again: the next attempt may succeed
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ApiError::Network(_) => true,
+            ApiError::Status(status) => *status >= 500,
+            ApiError::Parse(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Network(err) => write!(f, "network error: {}", err),
+            ApiError::Status(status) => write!(f, "http status {}", status),
+            ApiError::Parse(err) => write!(f, "invalid response: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+/// Path of the puzzle of a day below an endpoint
+fn puzzle_path(endpoint: &str, date: Option<NaiveDate>) -> String {
+    match date {
+        Some(date) => format!("{}/date/{}", endpoint, date.format("%Y-%m-%d")),
+        None => format!("{}/today", endpoint),
+    }
+}
+
+fn parse_error(err: gloo::net::Error) -> ApiError {
+    ApiError::Parse(err.to_string())
+}
+
+/// Client for the puzzle API, or for the static files when
+/// no API is configured.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiClient {
+    /// URL all requests are relative to, without a trailing slash
+    base: String,
+    server_mode: bool,
+}
 
-pub async fn load_wordlist() -> Result<Wordlist> {
-    let request = Request::get("/data/words.txt");
-    let response = request.send().await?;
-    let text = response.text().await?;
-    let wordlist: Wordlist = Wordlist::from(text);
-    Ok(wordlist)
+impl Default for ApiClient {
+    fn default() -> Self {
+        match API_URL {
+            Some(api) => ApiClient::new(api, true),
+            None => ApiClient::new(BASE_PATH.unwrap_or_default(), false),
+        }
+    }
 }
 
-impl Wordlist {
-    pub async fn fetch() -> Result<Wordlist> {
-        let request = Request::get("/data/words.txt");
-        let response = request.send().await?;
-        let text = response.text().await?;
-        let wordlist: Wordlist = Wordlist::from(text);
-        Ok(wordlist)
+impl ApiClient {
+    pub fn new(base: &str, server_mode: bool) -> Self {
+        ApiClient {
+            base: base.trim_end_matches('/').into(),
+            server_mode,
+        }
+    }
+
+    /// Client of the race relay, if there is one
+    pub fn relay() -> Option<Self> {
+        RELAY_URL.or(API_URL).map(|url| ApiClient::new(url, true))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base, path)
+    }
+
+    fn api_url(&self, path: &str, language: &Language) -> String {
+        self.url(&format!("api/v1/{}?lang={}", path, language.code()))
+    }
+
+    /// Send a request, built anew for every attempt. Network
+    /// and server errors of GET requests are retried with a
+    /// growing delay. Other requests may have been applied by
+    /// the server before failing, so they are sent only once.
+    /// Other responses are returned whatever their status.
+    async fn send<F>(&self, build: F) -> ApiResult<Response>
+    where
+        F: Fn() -> Result<Request, gloo::net::Error>,
+    {
+        let mut attempt = 0;
+        loop {
+            let request = build().map_err(parse_error)?;
+            let idempotent = request.method() == Method::GET;
+            let result = request
+                .send()
+                .await
+                .map_err(|err| ApiError::Network(err.to_string()));
+            let error = match result {
+                Ok(response) if response.status() < 500 => return Ok(response),
+                Ok(response) => ApiError::Status(response.status()),
+                Err(err) => err,
+            };
+            if attempt >= RETRIES || !idempotent || !error.is_transient() {
+                return Err(error);
+            }
+            TimeoutFuture::new(RETRY_DELAY << attempt).await;
+            attempt += 1;
+        }
+    }
+
+    /// Fail on any status besides success
+    fn expect_ok(response: Response) -> ApiResult<Response> {
+        match response.ok() {
+            true => Ok(response),
+            false => Err(ApiError::Status(response.status())),
+        }
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        build: fn(RequestBuilder) -> RequestBuilder,
+    ) -> ApiResult<T> {
+        let response = self.send(|| build(Request::get(url)).build()).await?;
+        let response = Self::expect_ok(response)?;
+        response.json().await.map_err(parse_error)
+    }
+
+    async fn post_json<B: Serialize>(
+        &self,
+        url: &str,
+        body: &B,
+    ) -> ApiResult<Response> {
+        self.send(|| Request::post(url).json(body)).await
+    }
+
+    /// Fetch the word list unless it has the given version.
+    /// Returns `None` if the version is up to date.
+    pub async fn refresh_wordlist(
+        &self,
+        language: &Language,
+        version: &str,
+    ) -> ApiResult<Option<Wordlist>> {
+        let url = match self.server_mode {
+            true => self.url(&format!("api/v1/wordlists/{}", language.code())),
+            false => self.url(&format!("{}/words.txt", language.data_path())),
+        };
+        let etag = format!("\"{}\"", version);
+        let response = self
+            .send(|| Request::get(&url).header("If-None-Match", &etag).build())
+            .await?;
+        if response.status() == 304 {
+            return Ok(None);
+        }
+        let text = Self::expect_ok(response)?
+            .text()
+            .await
+            .map_err(parse_error)?;
+        let wordlist = Wordlist::from(text);
+        Ok((wordlist.version() != version).then_some(wordlist))
+    }
+
+    /// Load the puzzle of a day with its solution, today's
+    /// if no date is given
+    pub async fn load_wordle(
+        &self,
+        language: &Language,
+        date: Option<NaiveDate>,
+    ) -> ApiResult<Wordle> {
+        let mut wordle: Wordle = match self.server_mode {
+            true => {
+                let url = self.api_url(&puzzle_path("puzzles", date), language);
+                self.get_json(&url, |request| request).await?
+            }
+            false => {
+                let date = date.unwrap_or_else(|| Utc::now().date_naive());
+                let url = self.url(&format!(
+                    "{}/{}.json",
+                    language.data_path(),
+                    date.format("%Y-%m-%d")
+                ));
+                self.get_json(&url, |request| {
+                    request.referrer("").mode(RequestMode::NoCors)
+                })
+                .await?
+            }
+        };
+        wordle.solution = language.fold(&wordle.solution);
+        Ok(wordle)
+    }
+
+    /// Load the puzzle of a day without the solution, today's
+    /// if no date is given
+    pub async fn load_puzzle(
+        &self,
+        language: &Language,
+        date: Option<NaiveDate>,
+    ) -> ApiResult<Puzzle> {
+        let url = self.api_url(&puzzle_path("play", date), language);
+        self.get_json(&url, |request| request).await
+    }
+
+    /// Submit a guess to be scored by the server. Words which
+    /// are not in the word list are rejected with `None`.
+    pub async fn submit_guess(
+        &self,
+        language: &Language,
+        id: u32,
+        guess: &Guess,
+    ) -> ApiResult<Option<Vec<Hint>>> {
+        let url = self.api_url(&format!("play/{}/guess", id), language);
+        let body = GuessRequest {
+            guess: guess.clone(),
+        };
+        let response = self.post_json(&url, &body).await?;
+        if response.status() == 422 {
+            return Ok(None);
+        }
+        let response = Self::expect_ok(response)?;
+        let GuessResponse { hints } =
+            response.json().await.map_err(parse_error)?;
+        Ok(Some(hints))
+    }
+
+    /// Ask for the solution of a finished game
+    pub async fn reveal_solution(
+        &self,
+        language: &Language,
+        id: u32,
+        guesses: &[Guess],
+    ) -> ApiResult<String> {
+        let url = self.api_url(&format!("play/{}/reveal", id), language);
+        let body = RevealRequest {
+            guesses: guesses.to_vec(),
+        };
+        let response = Self::expect_ok(self.post_json(&url, &body).await?)?;
+        let RevealResponse { solution } =
+            response.json().await.map_err(parse_error)?;
+        Ok(language.fold(&solution))
+    }
+
+    /// Submit the result of a puzzle to the leaderboard of a group
+    pub async fn submit_result(
+        &self,
+        language: &Language,
+        group: &str,
+        result: &ResultSubmission,
+    ) -> ApiResult<()> {
+        let path = format!("leaderboard/{}/results", group);
+        let url = self.api_url(&path, language);
+        Self::expect_ok(self.post_json(&url, result).await?)?;
+        Ok(())
+    }
+
+    /// Rankings of a group for a puzzle and over all puzzles
+    pub async fn load_leaderboard(
+        &self,
+        language: &Language,
+        group: &str,
+        id: u32,
+    ) -> ApiResult<Leaderboard> {
+        let path = format!("leaderboard/{}", group);
+        let url = format!("{}&id={}", self.api_url(&path, language), id);
+        self.get_json(&url, |request| request).await
+    }
+
+    /// Push the stored games and pull those of other
+    /// devices with the same sync code
+    pub async fn sync(
+        &self,
+        code: &str,
+        games: Vec<Game>,
+    ) -> ApiResult<Vec<Game>> {
+        let url = self.url(&format!("api/v1/sync/{}", code));
+        let response = self.post_json(&url, &SyncRequest { games }).await?;
+        let SyncResponse { games } = Self::expect_ok(response)?
+            .json()
+            .await
+            .map_err(parse_error)?;
+        Ok(games)
+    }
+
+    /// Open a race room on a random word of the language
+    pub async fn create_room(
+        &self,
+        language: &Language,
+    ) -> ApiResult<RoomInfo> {
+        let url = self.api_url("race", language);
+        let response = Self::expect_ok(self.post_json(&url, &()).await?)?;
+        response.json().await.map_err(parse_error)
+    }
+
+    /// WebSocket URL to join a race room
+    pub fn room_url(&self, code: &str, name: &str) -> String {
+        let url = self.url(&format!("api/v1/race/{}", code));
+        let url = match url.split_once("://") {
+            Some(("https", rest)) => format!("wss://{}", rest),
+            Some((_, rest)) => format!("ws://{}", rest),
+            None => url,
+        };
+        format!("{}?name={}", url, clean_name(name))
     }
 }
 
-#[derive(Debug, Deserialize, Default, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, Default, PartialEq, Clone)]
 pub struct Wordle {
     pub days_since_launch: usize,
     pub editor: String,
@@ -32,14 +371,96 @@ pub struct Wordle {
     pub solution: String,
 }
 
-pub async fn load_wordle() -> Result<Wordle> {
-    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-    let url = format!("data/{}.json", today);
-    let response = Request::get(&url)
-        .referrer("")
-        .mode(RequestMode::NoCors)
-        .send()
-        .await?;
-    let wordle: Wordle = response.json().await?;
-    Ok(wordle)
+/// A puzzle without its solution, as served in server mode
+#[derive(Debug, Deserialize, Serialize, Default, PartialEq, Clone)]
+pub struct Puzzle {
+    pub days_since_launch: usize,
+    pub editor: String,
+    pub id: u32,
+    pub print_date: String,
+    /// Number of letters of the solution
+    pub length: usize,
+}
+
+impl From<&Wordle> for Puzzle {
+    fn from(wordle: &Wordle) -> Self {
+        Puzzle {
+            days_since_launch: wordle.days_since_launch,
+            editor: wordle.editor.clone(),
+            id: wordle.id,
+            print_date: wordle.print_date.clone(),
+            length: wordle.solution.chars().count(),
+        }
+    }
+}
+
+impl From<Puzzle> for Wordle {
+    fn from(puzzle: Puzzle) -> Self {
+        Wordle {
+            days_since_launch: puzzle.days_since_launch,
+            editor: puzzle.editor,
+            id: puzzle.id,
+            print_date: puzzle.print_date,
+            solution: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GuessRequest {
+    pub guess: Guess,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GuessResponse {
+    pub hints: Vec<Hint>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevealRequest {
+    pub guesses: Vec<Guess>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevealResponse {
+    pub solution: String,
+}
+
+/// Result of a daily puzzle, submitted to a leaderboard
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ResultSubmission {
+    pub nickname: String,
+    /// Id of the puzzle
+    pub id: u32,
+    pub tries: usize,
+    pub solved: bool,
+    /// Solve time in milliseconds, if known
+    pub time: Option<i64>,
+    pub hard_mode: bool,
+}
+
+/// Rank of a player for one puzzle
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct DailyRank {
+    pub nickname: String,
+    pub tries: usize,
+    pub solved: bool,
+    pub time: Option<i64>,
+    pub hard_mode: bool,
+}
+
+/// Rank of a player over all puzzles
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct OverallRank {
+    pub nickname: String,
+    pub played: usize,
+    pub solved: usize,
+    /// Average tries of the solved puzzles
+    pub average_tries: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Default)]
+pub struct Leaderboard {
+    pub today: Vec<DailyRank>,
+    pub all_time: Vec<OverallRank>,
 }
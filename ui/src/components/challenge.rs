@@ -0,0 +1,88 @@
+use gloo::dialogs::alert;
+use yew::{function_component, html, use_state, Callback, Html};
+
+use crate::{
+    components::on_text,
+    i18n::use_i18n,
+    state::{
+        use_config, use_game_state, use_route_context, use_wordlist, Challenge,
+        Route, CHALLENGE_NAMESPACE,
+    },
+};
+
+fn copy(text: &str) {
+    let clipboard = web_sys::window()
+        .unwrap()
+        .navigator()
+        .clipboard()
+        .expect("clipboard required");
+    let _ = clipboard.write_text(text);
+}
+
+/// Create a link to a puzzle with a word of the player
+#[function_component]
+pub fn ChallengePanel() -> Html {
+    let i18n = use_i18n();
+    let language = use_config().language;
+    let wordlist = use_wordlist();
+    let game = use_game_state();
+    let route = use_route_context();
+    let word = use_state(String::new);
+    // The link, or the message key of the problem with the word
+    let link = use_state(|| None::<Result<String, &'static str>>);
+
+    let on_create = {
+        let word = word.clone();
+        let link = link.clone();
+        Callback::from(move |_| {
+            let challenge = Challenge::new(&language, &word, &wordlist);
+            link.set(Some(challenge.map(|challenge| challenge.url())));
+        })
+    };
+
+    let on_copy = {
+        let link = link.clone();
+        let copied = i18n.t("copied");
+        Callback::from(move |_| {
+            if let Some(Ok(url)) = &*link {
+                copy(url);
+                alert(&copied);
+            }
+        })
+    };
+
+    html! {
+        <>
+            if game.namespace == CHALLENGE_NAMESPACE {
+                <div class="row subtitle">
+                    { i18n.t("challenge-playing") }{ " " }
+                    <a href={Route::Daily.to_path()} onclick={route.link(Route::Daily)}>{ i18n.t("challenge-leave") }</a>
+                </div>
+            }
+            <div class="row subtitle">{ i18n.t("challenge-intro") }</div>
+            <label class="setting">
+                <b>{ i18n.t("challenge-word") }</b>
+                <input
+                    value={(*word).clone()}
+                    oninput={on_text(word.clone())} />
+            </label>
+            <div class="row share">
+                <button onclick={on_create}>{ i18n.t("challenge-create") }</button>
+            </div>
+            { match &*link {
+                Some(Ok(url)) => html! {
+                    <>
+                        <div class="row subtitle"><code>{ url }</code></div>
+                        <div class="row share">
+                            <button onclick={on_copy}>{ i18n.t("challenge-copy") }</button>
+                        </div>
+                    </>
+                },
+                Some(Err(key)) => html! {
+                    <div class="row subtitle">{ i18n.t(key) }</div>
+                },
+                None => html! {},
+            } }
+        </>
+    }
+}
@@ -1,24 +1,99 @@
-use yew::{function_component, html, Callback, Html, classes, use_state};
+use gloo::{events::EventListener, utils::document};
+use web_sys::{wasm_bindgen::JsCast, HtmlElement, KeyboardEvent};
+use yew::{
+    classes, function_component, html, use_effect_with, use_state, Callback,
+    Html,
+};
 
 use crate::{
-    components::{GuessBoard, KeyboardInput},
+    api,
+    components::{GuessBoard, KeyboardInput, Nav, Opponents, Timer},
+    i18n::use_i18n,
     state::{
-        game::State as GameState, use_game_context, use_wordlist, GameAction,
-        GameContext,
+        game::State as GameState, normalize, practice_word, use_config,
+        use_game_context, use_modal, use_result_submission, use_settings,
+        use_sync, use_wordlist, GameAction, GameContext, KEY_BKSP, KEY_CLEAR,
+        KEY_ENTER,
     },
+    task::spawn,
 };
 
+/// Map a physical key to a glyph of the on-screen keyboard.
+fn glyph_for_key(event: &KeyboardEvent, glyphs: &[String]) -> Option<String> {
+    if event.ctrl_key() || event.meta_key() || event.alt_key() {
+        return None;
+    }
+    match event.key().as_str() {
+        "Enter" => Some(KEY_ENTER.into()),
+        "Backspace" => Some(KEY_BKSP.into()),
+        "Escape" | "Delete" => Some(KEY_CLEAR.into()),
+        key => {
+            let glyph = normalize(key);
+            glyphs.contains(&glyph).then_some(glyph)
+        }
+    }
+}
+
+/// Buttons and links handle Enter and Space by themselves.
+fn is_interactive(event: &KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        .map(|el| matches!(el.tag_name().as_str(), "BUTTON" | "A" | "INPUT"))
+        .unwrap_or(false)
+}
+
 #[function_component]
 pub fn Game() -> Html {
     let wordlist = use_wordlist();
+    let settings = use_settings();
+    let language = use_config().language;
+    let i18n = use_i18n();
+    let modal = use_modal();
     let GameContext { game, dispatch } = use_game_context();
+    use_result_submission();
+    use_sync();
 
     // TODO: Bit lazy and hacky.. should be done better
     let gameclass = use_state(|| "".to_string());
 
+    // Text of the live region, read by screen readers
+    let announcement = use_state(|| "".to_string());
+
+    // Shake the board, announce and record why a guess was rejected
+    let reject = {
+        let gameclass = gameclass.clone();
+        let announcement = announcement.clone();
+        let i18n = i18n.clone();
+        let dispatch = dispatch.clone();
+        Callback::from(move |reason: &'static str| {
+            gameclass.set(reason.to_string());
+            announcement.set(i18n.t(reason));
+            dispatch.emit(GameAction::Reject(reason.into()));
+        })
+    };
+
+    let on_action = |action: GameAction| {
+        let dispatch = dispatch.clone();
+        Callback::from(move |_| dispatch.emit(action.clone()))
+    };
+    let on_undo = on_action(GameAction::UndoGuess);
+    let on_reset = on_action(GameAction::Reset);
+    let on_new_word = {
+        let dispatch = dispatch.clone();
+        let wordlist = wordlist.clone();
+        let language = language.clone();
+        Callback::from(move |_| {
+            let word = practice_word(&wordlist);
+            dispatch.emit(GameAction::Practice(language.fold(&word)));
+        })
+    };
+
     let on_input = {
         let game = game.clone();
         let gameclass = gameclass.clone();
+        let hard_mode = settings.hard_mode;
+        let language = language.clone();
         Callback::from(move |key: String| {
             if game.state != GameState::Running {
                 return;
@@ -27,19 +102,44 @@ pub fn Game() -> Html {
             gameclass.set("".to_string());
             let mut word = game.current.to_string();
             match key.as_str() {
-                "BKSP" => {
+                KEY_BKSP => {
                     word.pop();
                     dispatch.emit(GameAction::SetCurrent(word));
                 }
-                "ENTER" => {
-                    if wordlist.contains(&word) {
-                        dispatch.emit(GameAction::AddGuess);
+                KEY_CLEAR => dispatch.emit(GameAction::ClearCurrent),
+                KEY_ENTER => {
+                    if game.current.len() != game.word_length() {
+                        reject.emit("error-too-short");
+                    } else if !wordlist.contains(&word) {
+                        reject.emit("error-not-a-word");
+                    } else if hard_mode
+                        && !game.satisfies_hard_mode(&game.current)
+                    {
+                        reject.emit("error-hard-mode");
+                    } else if api::server_mode() && game.solution.is_empty() {
+                        let guess = game.current.clone();
+                        let language = language.clone();
+                        let id = game.id;
+                        let dispatch = dispatch.clone();
+                        let reject = reject.clone();
+                        spawn(async move {
+                            match api::ApiClient::default()
+                                .submit_guess(&language, id, &guess)
+                                .await?
+                            {
+                                Some(hints) => dispatch.emit(
+                                    GameAction::AddScoredGuess(guess, hints),
+                                ),
+                                None => reject.emit("error-not-a-word"),
+                            }
+                            Ok(())
+                        });
                     } else {
-                        gameclass.set("error-not-a-word".to_string());
+                        dispatch.emit(GameAction::AddGuess);
                     }
                 }
                 _ => {
-                    if word.len() < game.solution.len() {
+                    if game.current.len() < game.word_length() {
                         word.push_str(&key);
                         dispatch.emit(GameAction::SetCurrent(word));
                     }
@@ -48,14 +148,105 @@ pub fn Game() -> Html {
         })
     };
 
+    // Announce the result of each guess
+    {
+        let announcement = announcement.clone();
+        let game = game.clone();
+        let i18n = i18n.clone();
+        use_effect_with(game.guesses.len(), move |_| {
+            let hints = game.guess_hints();
+            if let (Some(guess), Some(hints)) =
+                (game.guesses.last(), hints.last())
+            {
+                let text = guess.describe(hints, &i18n);
+                let result = match game.state {
+                    GameState::Win => Some(i18n.t("title-win")),
+                    GameState::Loss => Some(i18n.t("title-loss")),
+                    GameState::Running => None,
+                };
+                match result {
+                    Some(result) => announcement.set(text + ". " + &result),
+                    None => announcement.set(text),
+                }
+            }
+        });
+    }
+
+    // Play with the physical keyboard
+    {
+        let glyphs: Vec<String> = settings
+            .keyboard_layout
+            .rows_with(language.extra_keys())
+            .into_iter()
+            .flatten()
+            .map(String::from)
+            .collect();
+        use_effect_with(
+            (on_input.clone(), modal.open),
+            move |(on_input, open)| {
+                let on_input = on_input.clone();
+                // Dialogs handle the keyboard on their own
+                let listener = open.is_none().then(|| {
+                    EventListener::new(&document(), "keydown", move |event| {
+                        let event = event.dyn_ref::<KeyboardEvent>().unwrap();
+                        if is_interactive(event)
+                            && matches!(event.key().as_str(), "Enter" | " ")
+                        {
+                            return;
+                        }
+                        if let Some(glyph) = glyph_for_key(event, &glyphs) {
+                            event.prevent_default();
+                            on_input.emit(glyph);
+                        }
+                    })
+                });
+                move || drop(listener)
+            },
+        );
+    }
+
     html! {
-        <div class={classes!("game", (*gameclass).clone())}>
+        <div class={classes!(
+            "game",
+            (*gameclass).clone(),
+            settings.to_css_classes(),
+        )}>
+            <div class="container">
+                <Nav />
+            </div>
+            if settings.speed_mode {
+                <div class="container">
+                    <Timer />
+                </div>
+            }
             <div class="container">
                 <GuessBoard />
             </div>
             <div class="container">
                 <KeyboardInput on_input={on_input} />
             </div>
+            <div class="container">
+                <Opponents />
+            </div>
+            if game.practice || cfg!(debug_assertions) {
+                <div class="container actions">
+                    if game.practice {
+                        <button
+                            disabled={game.guesses.is_empty()}
+                            onclick={on_undo}>
+                            { i18n.t("undo") }
+                        </button>
+                        <button onclick={on_new_word}>{ i18n.t("new-word") }</button>
+                    }
+                    // Only in debug builds
+                    if cfg!(debug_assertions) {
+                        <button onclick={on_reset}>{ i18n.t("reset-today") }</button>
+                    }
+                </div>
+            }
+            <div class="sr-only" role="status" aria-live="polite">
+                { (*announcement).clone() }
+            </div>
         </div>
     }
 }
@@ -1,14 +1,25 @@
-use gloo::{
-    dialogs::alert,
-    timers::callback::Timeout,
-};
+use gloo::dialogs::alert;
 use yew::{
-    classes, function_component, html, use_callback, use_effect_with,
-    use_state, Callback, Html, Properties,
+    classes, function_component, html, use_callback, use_effect_with, Callback,
+    Html, Properties,
 };
 
-use crate::state::game::{
-    use_game_state, Game, Guess, History as GameHistory, State as GameState,
+use crate::{
+    components::{
+        ChallengePanel, Help, LeaderboardPanel, Modal, RacePanel, Replay,
+        SettingsPanel,
+    },
+    i18n::{use_i18n, I18n},
+    state::{
+        format_time,
+        game::{
+            use_game_state, Game, Guess, Hint, History as GameHistory,
+            State as GameState,
+        },
+        leaderboard_available, race_available, use_modal, use_route,
+        use_settings, Challenge, ModalAction, ModalKind, Route,
+        CHALLENGE_NAMESPACE,
+    },
 };
 
 #[derive(Properties, Clone, PartialEq)]
@@ -44,73 +55,113 @@ pub fn Histogram(props: &HistogramProps) -> Html {
     }
 }
 
-#[derive(Properties, Clone, PartialEq)]
-pub struct StatsProps {
-    #[prop_or_default]
-    pub visible: bool,
+/// Title of the stats, depending on the outcome of the game
+pub fn stats_title(game: &Game, i18n: &I18n) -> String {
+    match game.state {
+        GameState::Win => i18n.t("title-win"),
+        GameState::Loss => i18n.t("title-loss"),
+        GameState::Running => i18n.t("title-running"),
+    }
 }
 
 #[function_component]
-pub fn Stats(props: &StatsProps) -> Html {
-    let StatsProps { visible } = props;
+pub fn Stats() -> Html {
     let game = use_game_state();
-    let history = GameHistory::new();
+    let i18n = use_i18n();
+    let history = GameHistory::for_namespace(&game.namespace);
 
-    let title = match game.state {
-        GameState::Win => "Congratulations!",
-        GameState::Loss => "You lost!",
-        GameState::Running => "Good luck!",
+    let subtitle = match game.state {
+        GameState::Win => Some(i18n.tn("solved-in", game.tries())),
+        _ => None,
     };
 
-    let cls = match visible {
-        true => vec!["stats", "visible"],
-        false => vec!["stats"],
-    };
+    let solve_time = game.solve_time().map(format_time);
+    let speed_mode = use_settings().speed_mode;
+    let fastest = history
+        .fastest(5)
+        .into_iter()
+        .map(|(game, time)| {
+            html! {
+                <li>
+                    <span>{ format!("#{}", game.id) }</span>
+                    <span>{ format_time(time) }</span>
+                </li>
+            }
+        })
+        .collect::<Html>();
 
     let streak = history.streak();
     let max_streak = history.max_streak();
     let played = history.played();
     let histogram = history.histogram();
 
-    let on_share = use_callback(game, move |_, game| {
+    let can_replay =
+        game.state != GameState::Running && !game.events.is_empty();
+    let on_replay = {
+        let modal = use_modal();
+        Callback::from(move |_| {
+            modal.dispatch(ModalAction::Open(ModalKind::Replay))
+        })
+    };
+
+    let copied = i18n.t("copied");
+    let route = use_route();
+    let on_share = use_callback((game, route), move |_, (game, route)| {
         let clipboard = web_sys::window()
             .unwrap()
             .navigator()
             .clipboard()
             .expect("clipboard required");
-        let share = game.to_share();
+        // Pass challenges on with the results
+        let share = match Challenge::from_route(route) {
+            Some(challenge) if game.namespace == CHALLENGE_NAMESPACE => {
+                format!("{}\n{}", game.to_share(), challenge.url())
+            }
+            _ => game.to_share(),
+        };
         let _ = clipboard.write_text(&share);
-        alert("Copied to clipboard!");
+        alert(&copied);
     });
 
     html! {
-        <div class={classes!(cls)}>
-            <div class="rows">
-                <div class="row">
-                    <h1>{ title }</h1>
-                </div>
-                <div class="row">
-                    <div class="stat">
-                        <b>{ "Played" }</b>
-                        <span>{ played }</span>
-                    </div>
-                    <div class="stat">
-                        <b>{ "Current Streak" }</b>
-                        <span>{ streak }</span>
-                    </div>
-                    <div class="stat">
-                        <b>{ "Max Streak" }</b>
-                        <span>{ max_streak }</span>
-                    </div>
+        <>
+            if let Some(subtitle) = subtitle {
+                <div class="row subtitle">{ subtitle }</div>
+            }
+            <div class="row">
+                <div class="stat">
+                    <b>{ i18n.t("played") }</b>
+                    <span>{ played }</span>
                 </div>
-                <div class="row histogram">
-                    <Histogram total={played} histogram={histogram} />
+                <div class="stat">
+                    <b>{ i18n.t("current-streak") }</b>
+                    <span>{ streak }</span>
                 </div>
-                <div class="row share">
-                    <button onclick={on_share}>{ "Share" }</button>
+                <div class="stat">
+                    <b>{ i18n.t("max-streak") }</b>
+                    <span>{ max_streak }</span>
                 </div>
+                if let Some(solve_time) = solve_time {
+                    <div class="stat">
+                        <b>{ i18n.t("time") }</b>
+                        <span>{ solve_time }</span>
+                    </div>
+                }
             </div>
-        </div>
+            if speed_mode {
+                <div class="row subtitle">{ i18n.t("fastest") }</div>
+                <ol class="row leaderboard">{ fastest }</ol>
+            }
+            <div class="row histogram">
+                <Histogram total={played} histogram={histogram} />
+            </div>
+            <div class="row share">
+                <button onclick={on_share}>{ i18n.t("share") }</button>
+                if can_replay {
+                    <button onclick={on_replay}>{ i18n.t("replay") }</button>
+                }
+            </div>
+        </>
     }
 }
 
@@ -118,39 +169,62 @@ pub fn Stats(props: &StatsProps) -> Html {
 pub struct GuessViewProps {
     #[prop_or_default]
     pub guess: Guess,
+    /// Hints to show and announce, none for the current guess
+    #[prop_or_default]
+    pub hints: Option<Vec<Hint>>,
 }
 
 #[function_component]
 pub fn GuessView(props: &GuessViewProps) -> Html {
-    let Game { solution, .. } = use_game_state();
-    let GuessViewProps { guess } = props;
-    let hints = guess.hints(&solution);
+    let game = use_game_state();
+    let i18n = use_i18n();
+    let GuessViewProps { guess, hints } = props;
 
-    let char_count = guess.to_string().chars().take(5).count();
+    let length = match hints {
+        Some(hints) => hints.len(),
+        None => game.word_length(),
+    };
+    let char_count = guess.len().min(length);
 
     // Zip chars with hints
     let chars = guess
         .to_string()
         .chars()
-        .zip(
-            hints
-                .iter()
-                .map(|h| h.to_css_class())
-                .collect::<Vec<String>>(),
-        )
-        .map(|(c, hint)| {
+        .take(char_count)
+        .enumerate()
+        .map(|(i, c)| {
+            let hint = hints.as_ref().and_then(|hints| hints.get(i));
+            let label = match hint {
+                Some(hint) => {
+                    format!("{}, {}", c, i18n.t(hint.to_message_key()))
+                }
+                None => c.to_string(),
+            };
             html! {
-                <span class={hint}>{c}</span>
+                <span
+                    class={hint.map(Hint::to_css_class)}
+                    role="gridcell"
+                    aria-label={label}>
+                    {c}
+                </span>
             }
         })
         .collect::<Html>();
 
-    let pad = (0..(5 - char_count))
-        .map(|_| html! { <span class="pad"></span> })
+    let pad = (0..(length - char_count))
+        .map(|_| {
+            html! {
+                <span
+                    class="pad"
+                    role="gridcell"
+                    aria-label={i18n.t("empty")}>
+                </span>
+            }
+        })
         .collect::<Html>();
 
     html! {
-        <div class="guess">
+        <div class="guess" role="row">
             { chars }
             { pad }
         </div>
@@ -159,13 +233,16 @@ pub fn GuessView(props: &GuessViewProps) -> Html {
 
 #[function_component]
 pub fn GuessBoard() -> Html {
-    let show_stats = use_state(|| false);
+    let modal = use_modal();
+    let route = use_route();
+    let i18n = use_i18n();
+    let game = use_game_state();
     let Game {
         state,
         guesses,
         current,
         ..
-    } = use_game_state();
+    } = game.clone();
     let max_guesses = 6;
     let pad = if guesses.len() < max_guesses {
         (0..(max_guesses - guesses.len() - 1))
@@ -175,48 +252,106 @@ pub fn GuessBoard() -> Html {
         html! {}
     };
 
-    let on_click_stats = {
-        let show_stats = show_stats.clone();
-        Callback::from(move |_| show_stats.set(!*show_stats))
+    let on_click = |kind: ModalKind| {
+        let modal = modal.clone();
+        Callback::from(move |_| modal.dispatch(ModalAction::Toggle(kind)))
     };
 
+    // Show the stats shortly after the game ended
     {
-        let show_stats = show_stats.clone();
-        let state = state.clone();
-        use_effect_with(state, move |state| {
-            let timer = match state {
-                GameState::Running => {
-                    show_stats.set(false);
-                    None
-                }
-                _ => {
-                // Set a timer:
-                let t = Timeout::new(2100, move || {
-                    show_stats.set(true);
-                });
-                Some(t)
+        let modal = modal.clone();
+        use_effect_with(state.clone(), move |state| match state {
+            GameState::Running => {
+                if modal.is_open(ModalKind::Stats) {
+                    modal.dispatch(ModalAction::Close);
                 }
-            };
-            || { timer.map(|t| t.forget()); }   
+            }
+            _ => modal.dispatch(ModalAction::OpenAfter(ModalKind::Stats, 2100)),
+        });
+    }
+
+    // The stats have a link of their own
+    {
+        let modal = modal.clone();
+        use_effect_with(route, move |route| {
+            if *route == Route::Stats {
+                modal.dispatch(ModalAction::Open(ModalKind::Stats));
+            }
         });
     }
 
-    let stats_cls = match *show_stats {
-        true => "stats",
-        false => "",
+    let stats_cls = match modal.open {
+        Some(_) => "stats",
+        None => "",
     };
 
     html! {
         <div class="main">
             <div class="tabs">
-                <button onclick={on_click_stats}>{ "Statistics" }</button>
+                <button
+                    aria-expanded={modal.is_open(ModalKind::Help).to_string()}
+                    onclick={on_click(ModalKind::Help)}>
+                    { i18n.t("help") }
+                </button>
+                <button
+                    aria-expanded={modal.is_open(ModalKind::Stats).to_string()}
+                    onclick={on_click(ModalKind::Stats)}>
+                    { i18n.t("statistics") }
+                </button>
+                <button
+                    aria-expanded={modal.is_open(ModalKind::Settings).to_string()}
+                    onclick={on_click(ModalKind::Settings)}>
+                    { i18n.t("settings") }
+                </button>
+                <button
+                    aria-expanded={modal.is_open(ModalKind::Challenge).to_string()}
+                    onclick={on_click(ModalKind::Challenge)}>
+                    { i18n.t("challenge") }
+                </button>
+                if leaderboard_available() {
+                    <button
+                        aria-expanded={modal.is_open(ModalKind::Leaderboard).to_string()}
+                        onclick={on_click(ModalKind::Leaderboard)}>
+                        { i18n.t("leaderboard") }
+                    </button>
+                }
+                if race_available() {
+                    <button
+                        aria-expanded={modal.is_open(ModalKind::Race).to_string()}
+                        onclick={on_click(ModalKind::Race)}>
+                        { i18n.t("race") }
+                    </button>
+                }
             </div>
         <div class={classes!("guess-board", state.to_css_class(), stats_cls)}>
-            <Stats visible={*show_stats} />
-            <div class="guesses">
+            <Modal kind={ModalKind::Stats} title={stats_title(&game, &i18n)}>
+                <Stats />
+            </Modal>
+            <Modal kind={ModalKind::Help} title={i18n.t("help-title")}>
+                <Help />
+            </Modal>
+            <Modal kind={ModalKind::Settings} title={i18n.t("settings")}>
+                <SettingsPanel />
+            </Modal>
+            <Modal kind={ModalKind::Replay} title={i18n.t("replay")}>
+                <Replay />
+            </Modal>
+            <Modal kind={ModalKind::Challenge} title={i18n.t("challenge")}>
+                <ChallengePanel />
+            </Modal>
+            <Modal kind={ModalKind::Leaderboard} title={i18n.t("leaderboard")}>
+                <LeaderboardPanel />
+            </Modal>
+            <Modal kind={ModalKind::Race} title={i18n.t("race")}>
+                <RacePanel />
+            </Modal>
+            <div class="guesses" role="grid" aria-label={i18n.t("guesses")}>
                 <div class="history">
-                    { for guesses.iter().map(|guess| html! {
-                        <GuessView key={guess.to_string()} guess={guess.clone()} /> }) }
+                    { for guesses.iter().zip(game.guess_hints()).map(|(guess, hints)| html! {
+                        <GuessView
+                            key={guess.to_string()}
+                            guess={guess.clone()}
+                            hints={hints} /> }) }
                 </div>
                 <div class="current">
                 if state != GameState::Loss {
@@ -0,0 +1,45 @@
+use yew::{function_component, html, Html};
+
+use crate::{components::GuessView, i18n::use_i18n, state::Guess};
+
+/// Example guesses, each scored against a solution
+/// revealing exactly one kind of hint.
+const EXAMPLES: [(&str, &str, char, &str); 3] = [
+    ("WEARY", "WOULD", 'W', "help-example-correct"),
+    ("PILLS", "CHAIN", 'I', "help-example-misplaced"),
+    ("VAGUE", "CHIRP", 'U', "help-example-incorrect"),
+];
+
+#[function_component]
+pub fn Help() -> Html {
+    let i18n = use_i18n();
+
+    let examples = EXAMPLES
+        .iter()
+        .map(|(guess, solution, letter, key)| {
+            let text = i18n.t(key).replace("{letter}", &letter.to_string());
+            html! {
+                <div class="example">
+                    <GuessView
+                        guess={Guess::from(*guess)}
+                        hints={Guess::from(*guess).hints(solution)} />
+                    <p>{ text }</p>
+                </div>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="row help-text">
+            <p>{ i18n.t("help-goal") }</p>
+            <ul>
+                <li>{ i18n.t("help-rule-word") }</li>
+                <li>{ i18n.t("help-rule-hints") }</li>
+            </ul>
+            <b>{ i18n.t("help-examples") }</b>
+            <div class="history examples">
+                { examples }
+            </div>
+        </div>
+    }
+}
@@ -3,15 +3,21 @@ use yew::{
     classes, functional::function_component, html, Callback, Html, Properties,
 };
 
-use crate::state::{use_game_state, Game, Hint};
+use crate::{
+    i18n::use_i18n,
+    state::{
+        normalize, use_config, use_game_state, use_settings, Game, Hint,
+        KEY_BKSP, KEY_ENTER,
+    },
+};
 
-#[derive(Clone, PartialEq, Default)]
+#[derive(Clone, PartialEq, PartialOrd, Default)]
 pub enum KeyState {
     #[default]
     Unused,
     Incorrect,
-    Correct,
     Misplaced,
+    Correct,
 }
 
 #[derive(Properties, Clone, PartialEq)]
@@ -25,6 +31,7 @@ pub struct KeyboardKeyProps {
 
 #[function_component]
 pub fn KeyboardKey(props: &KeyboardKeyProps) -> Html {
+    let i18n = use_i18n();
     let glyph = props.glyph.clone();
     let state = match props.state {
         KeyState::Unused => "unused",
@@ -32,6 +39,22 @@ pub fn KeyboardKey(props: &KeyboardKeyProps) -> Html {
         KeyState::Correct => "correct",
         KeyState::Misplaced => "misplaced",
     };
+    let label = match glyph.as_str() {
+        KEY_ENTER => i18n.t("key-enter"),
+        KEY_BKSP => i18n.t("key-bksp"),
+        _ => match props.state {
+            KeyState::Unused => glyph.clone(),
+            KeyState::Incorrect => {
+                format!("{}, {}", glyph, i18n.t("hint-incorrect"))
+            }
+            KeyState::Correct => {
+                format!("{}, {}", glyph, i18n.t("hint-correct"))
+            }
+            KeyState::Misplaced => {
+                format!("{}, {}", glyph, i18n.t("hint-misplaced"))
+            }
+        },
+    };
     let on_click = {
         let glyph = glyph.clone();
         let on_click = props.on_click.clone();
@@ -45,6 +68,7 @@ pub fn KeyboardKey(props: &KeyboardKeyProps) -> Html {
     html! {
         <button
             class={classes!("key", id, state)}
+            aria-label={label}
             onclick={on_click}>
             { glyph }
         </button>
@@ -56,25 +80,21 @@ pub type KeyboardState = HashMap<String, KeyState>;
 impl From<&Game> for KeyboardState {
     fn from(game: &Game) -> Self {
         let mut state = Self::default();
-        let Game {
-            guesses, solution, ..
-        } = game;
         // Iterate over all characters in all guesses
-        for guess in guesses.iter() {
-            let hints = guess.hints(&solution);
+        for (guess, hints) in game.guesses.iter().zip(game.guess_hints()) {
             // Iterate over zipped chars with hints
-            for (key, hint) in guess.to_string().chars().zip(hints) {
-                // If the hint is correct, set the state to correct
-                match hint {
-                    Hint::Incorrect => {
-                        state.insert(key.into(), KeyState::Incorrect);
-                    }
-                    Hint::Correct => {
-                        state.insert(key.into(), KeyState::Correct);
-                    }
-                    Hint::Misplaced => {
-                        state.insert(key.into(), KeyState::Misplaced);
-                    }
+            let letters = normalize(&guess.to_string());
+            for (key, hint) in letters.chars().zip(hints) {
+                let key_state = match hint {
+                    Hint::Incorrect => KeyState::Incorrect,
+                    Hint::Correct => KeyState::Correct,
+                    Hint::Misplaced => KeyState::Misplaced,
+                };
+                // A key only ever moves up from incorrect
+                // to misplaced to correct.
+                let current = state.entry(key.into()).or_default();
+                if key_state > *current {
+                    *current = key_state;
                 }
             }
         }
@@ -92,12 +112,10 @@ pub struct KeyboardProps {
 #[function_component]
 pub fn KeyboardInput(props: &KeyboardProps) -> Html {
     let game = use_game_state();
-
-    let rows = vec![
-        vec!["Q", "W", "E", "R", "T", "Y", "U", "I", "O", "P"],
-        vec!["A", "S", "D", "F", "G", "H", "J", "K", "L"],
-        vec!["ENTER", "Z", "X", "C", "V", "B", "N", "M", "BKSP"],
-    ];
+    let settings = use_settings();
+    let language = use_config().language;
+    let i18n = use_i18n();
+    let rows = settings.keyboard_layout.rows_with(language.extra_keys());
 
     let state = KeyboardState::from(&game);
     let on_input = props.on_input.clone();
@@ -107,7 +125,7 @@ pub fn KeyboardInput(props: &KeyboardProps) -> Html {
     });
 
     html! {
-        <div class="keyboard">
+        <div class="keyboard" role="group" aria-label={i18n.t("keyboard")}>
             { for rows.iter().map(|row| {
                 html! {
                     <div class="keyboard-row">
@@ -0,0 +1,176 @@
+use yew::{function_component, html, use_state, Callback, Html};
+
+use crate::{
+    api::{self, clean_name, Leaderboard},
+    components::on_text,
+    i18n::use_i18n,
+    state::{
+        format_time, use_config, use_game_state, use_modal, use_resource,
+        use_settings_context, ModalKind, Resource,
+    },
+};
+
+/// Rankings of the group, for today's puzzle and for all
+/// puzzles
+#[function_component]
+pub fn Rankings() -> Html {
+    let i18n = use_i18n();
+    let settings = use_settings_context().settings;
+    let config = use_config();
+    let game = use_game_state();
+    let visible = use_modal().is_open(ModalKind::Leaderboard);
+
+    // Reload when opened and after the game is over
+    let key = (
+        visible,
+        settings.group.clone(),
+        config.language.clone(),
+        config.wordle.id,
+        game.state.clone(),
+    );
+    let leaderboard =
+        use_resource(key, |(visible, group, language, id, _)| async move {
+            if !visible {
+                return Ok(Leaderboard::default());
+            }
+            let leaderboard = api::ApiClient::default()
+                .load_leaderboard(&language, &group, id)
+                .await?;
+            Ok(leaderboard)
+        });
+
+    let leaderboard = match &leaderboard.state {
+        Resource::Loading => {
+            return html! { <div class="row subtitle">{ i18n.t("loading") }</div> }
+        }
+        Resource::Failed(message) => {
+            return html! {
+                <div class="row subtitle">
+                    { i18n.t("error-load") }{ ": " }{ message }
+                </div>
+            }
+        }
+        Resource::Ready(leaderboard) => leaderboard,
+    };
+
+    let today = leaderboard
+        .today
+        .iter()
+        .map(|rank| {
+            let result = match rank.solved {
+                true => format!("{}/6", rank.tries),
+                false => "X/6".into(),
+            };
+            html! {
+                <li>
+                    <span>{ &rank.nickname }</span>
+                    if rank.hard_mode {
+                        <span title={i18n.t("setting-hard-mode")}>{ "*" }</span>
+                    }
+                    <span>
+                        { result }
+                        if let Some(time) = rank.time {
+                            { " ⏱ " }{ format_time(time) }
+                        }
+                    </span>
+                </li>
+            }
+        })
+        .collect::<Html>();
+
+    let all_time = leaderboard
+        .all_time
+        .iter()
+        .map(|rank| {
+            let average = rank
+                .average_tries
+                .map(|tries| format!(" ⌀ {:.1}", tries))
+                .unwrap_or_default();
+            html! {
+                <li>
+                    <span>{ &rank.nickname }</span>
+                    <span>{ format!("{}/{}{}", rank.solved, rank.played, average) }</span>
+                </li>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+            <div class="row subtitle">{ i18n.t("leaderboard-today") }</div>
+            <ol class="row leaderboard">{ today }</ol>
+            <div class="row subtitle">{ i18n.t("leaderboard-all-time") }</div>
+            <ol class="row leaderboard">{ all_time }</ol>
+        </>
+    }
+}
+
+/// Join the leaderboard of a group, or show its rankings
+#[function_component]
+pub fn LeaderboardPanel() -> Html {
+    let i18n = use_i18n();
+    let ctx = use_settings_context();
+    let nickname = use_state(|| ctx.settings.nickname.clone());
+    let group = use_state(|| ctx.settings.group.clone());
+
+    let on_join = {
+        let ctx = ctx.clone();
+        let nickname = nickname.clone();
+        let group = group.clone();
+        Callback::from(move |_| {
+            let nickname = clean_name(&nickname);
+            let group = clean_name(&group);
+            ctx.update(|settings| {
+                settings.nickname = nickname;
+                settings.group = group;
+            })
+        })
+    };
+    let on_leave = {
+        let ctx = ctx.clone();
+        Callback::from(move |_| ctx.update(|settings| settings.group.clear()))
+    };
+
+    if ctx.settings.leaderboard_enabled() {
+        return html! {
+            <>
+                <div class="row subtitle">
+                    { i18n.t("leaderboard-group") }{ ": " }
+                    <b>{ &ctx.settings.group }</b>
+                    { " · " }{ &ctx.settings.nickname }
+                </div>
+                <Rankings />
+                <div class="row share">
+                    <button onclick={on_leave}>{ i18n.t("leaderboard-leave") }</button>
+                </div>
+            </>
+        };
+    }
+
+    let valid =
+        !clean_name(&nickname).is_empty() && !clean_name(&group).is_empty();
+    html! {
+        <>
+            <div class="row subtitle">{ i18n.t("leaderboard-intro") }</div>
+            <label class="setting">
+                <b>{ i18n.t("leaderboard-nickname") }</b>
+                <input
+                    value={(*nickname).clone()}
+                    maxlength="16"
+                    oninput={on_text(nickname.clone())} />
+            </label>
+            <label class="setting">
+                <b>{ i18n.t("leaderboard-group") }</b>
+                <input
+                    value={(*group).clone()}
+                    maxlength="16"
+                    oninput={on_text(group.clone())} />
+            </label>
+            <div class="row share">
+                <button disabled={!valid} onclick={on_join}>
+                    { i18n.t("leaderboard-join") }
+                </button>
+            </div>
+        </>
+    }
+}
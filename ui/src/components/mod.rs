@@ -6,3 +6,39 @@ pub use guessboard::*;
 
 mod game;
 pub use game::*;
+
+mod settings;
+pub use settings::*;
+
+mod modal;
+pub use modal::*;
+
+mod help;
+pub use help::*;
+
+mod status;
+pub use status::*;
+
+mod replay;
+pub use replay::*;
+
+mod timer;
+pub use timer::*;
+
+mod race;
+pub use race::*;
+
+mod challenge;
+pub use challenge::*;
+
+mod nav;
+pub use nav::*;
+
+mod leaderboard;
+pub use leaderboard::*;
+
+mod sync;
+pub use sync::*;
+
+mod update;
+pub use update::*;
@@ -0,0 +1,111 @@
+use gloo::{events::EventListener, utils::document};
+use web_sys::{wasm_bindgen::JsCast, HtmlElement, KeyboardEvent, MouseEvent};
+use yew::{
+    classes, function_component, html, use_effect_with, use_mut_ref,
+    use_node_ref, Callback, Children, Html, Properties,
+};
+
+use crate::{
+    i18n::use_i18n,
+    state::{use_modal, ModalAction, ModalKind},
+};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ModalProps {
+    pub kind: ModalKind,
+    pub title: String,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// An overlay dialog, opened and closed through the modal context.
+/// It is dismissed with Escape, a click on the backdrop or
+/// the close button, and returns the focus on close.
+#[function_component]
+pub fn Modal(props: &ModalProps) -> Html {
+    let ModalProps {
+        kind,
+        title,
+        children,
+    } = props;
+    let modal = use_modal();
+    let i18n = use_i18n();
+    let visible = modal.is_open(*kind);
+    let node = use_node_ref();
+
+    // Remember the focused element when opening and
+    // restore it when closing.
+    {
+        let node = node.clone();
+        let previous = use_mut_ref(|| None::<HtmlElement>);
+        use_effect_with(visible, move |visible| {
+            if *visible {
+                *previous.borrow_mut() = document()
+                    .active_element()
+                    .and_then(|el| el.dyn_into::<HtmlElement>().ok());
+                if let Some(el) = node.cast::<HtmlElement>() {
+                    let _ = el.focus();
+                }
+            } else if let Some(el) = previous.borrow_mut().take() {
+                let _ = el.focus();
+            }
+        });
+    }
+
+    // Close on Escape
+    {
+        let dispatch = modal.dispatch.clone();
+        use_effect_with(visible, move |visible| {
+            let listener = visible.then(|| {
+                EventListener::new(&document(), "keydown", move |event| {
+                    let event = event.dyn_ref::<KeyboardEvent>().unwrap();
+                    if event.key() == "Escape" {
+                        dispatch.emit(ModalAction::Close);
+                    }
+                })
+            });
+            move || drop(listener)
+        });
+    }
+
+    let on_backdrop = {
+        let modal = modal.clone();
+        Callback::from(move |event: MouseEvent| {
+            if event.target() == event.current_target() {
+                modal.dispatch(ModalAction::Close);
+            }
+        })
+    };
+
+    let on_close = {
+        let modal = modal.clone();
+        Callback::from(move |_| modal.dispatch(ModalAction::Close))
+    };
+
+    let title_id = format!("{}-title", kind.to_css_class());
+
+    html! {
+        <div
+            class={classes!("modal", kind.to_css_class(), visible.then_some("visible"))}
+            ref={node}
+            role="dialog"
+            aria-modal="true"
+            aria-labelledby={title_id.clone()}
+            aria-hidden={(!visible).to_string()}
+            tabindex="-1"
+            onclick={on_backdrop}>
+            <div class="rows">
+                <div class="row">
+                    <h1 id={title_id}>{ title }</h1>
+                    <button
+                        class="close"
+                        aria-label={i18n.t("close")}
+                        onclick={on_close}>
+                        { "✕" }
+                    </button>
+                </div>
+                { children.clone() }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::{Days, NaiveDate, Utc};
+use yew::{function_component, html, Html};
+
+use crate::{
+    i18n::use_i18n,
+    state::{use_config, use_route_context, Route},
+};
+
+/// Links between the modes, and through the archive
+/// while playing a puzzle of the calendar
+#[function_component]
+pub fn Nav() -> Html {
+    let i18n = use_i18n();
+    let ctx = use_route_context();
+    let config = use_config();
+
+    let link = |route: Route, label: String| {
+        let current = (ctx.route == route).then_some("page");
+        html! {
+            <a
+                href={route.to_path()}
+                aria-current={current}
+                onclick={ctx.link(route.clone())}>
+                { label }
+            </a>
+        }
+    };
+
+    // Neighbours of the puzzle, but none of the future
+    let today = Utc::now().date_naive();
+    let date =
+        NaiveDate::parse_from_str(&config.wordle.print_date, "%Y-%m-%d").ok();
+    let previous = date.and_then(|date| date.checked_sub_days(Days::new(1)));
+    let next = date
+        .and_then(|date| date.checked_add_days(Days::new(1)))
+        .filter(|date| *date <= today);
+    let day = |date: NaiveDate| match date == today {
+        true => Route::Daily,
+        false => Route::Archive(date),
+    };
+
+    html! {
+        <nav class="nav">
+            { link(Route::Daily, i18n.t("today")) }
+            { link(Route::Practice, i18n.t("practice")) }
+            if ctx.route.is_daily() {
+                if let Some(date) = previous {
+                    { link(day(date), i18n.t("previous-day")) }
+                }
+                if let Some(date) = next {
+                    { link(day(date), i18n.t("next-day")) }
+                }
+            }
+        </nav>
+    }
+}
@@ -0,0 +1,167 @@
+use web_sys::HtmlInputElement;
+use yew::{
+    function_component, html, use_state, Callback, Html, InputEvent,
+    Properties, TargetCast,
+};
+
+use crate::{
+    i18n::use_i18n,
+    state::{use_race, Player, RaceAction, RaceStatus, State as GameState},
+};
+
+/// Keep the value of a text input in a state
+pub fn on_text(value: yew::UseStateHandle<String>) -> Callback<InputEvent> {
+    Callback::from(move |event: InputEvent| {
+        let input: HtmlInputElement = event.target_unchecked_into();
+        value.set(input.value());
+    })
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct OpponentProps {
+    pub player: Player,
+}
+
+/// Board of an opponent: the hints without the letters
+#[function_component]
+pub fn Opponent(props: &OpponentProps) -> Html {
+    let i18n = use_i18n();
+    let Player {
+        name, hints, state, ..
+    } = &props.player;
+    let result = match state {
+        GameState::Win => i18n.t("race-solved"),
+        GameState::Loss => i18n.t("race-failed"),
+        GameState::Running => i18n.tn("race-tries", hints.len()),
+    };
+
+    html! {
+        <div class="opponent">
+            <b>{ name }</b>
+            <span>{ result }</span>
+            { for hints.iter().map(|hints| html! {
+                <div class="guess">
+                    { for hints.iter().map(|hint| html! {
+                        <span
+                            class={hint.to_css_class()}
+                            aria-label={i18n.t(hint.to_message_key())}>
+                        </span>
+                    }) }
+                </div>
+            }) }
+        </div>
+    }
+}
+
+/// Boards of the other players of the race
+#[function_component]
+pub fn Opponents() -> Html {
+    let race = use_race();
+    let Some(room) = race.room() else {
+        return html! {};
+    };
+
+    html! {
+        <div class="opponents">
+            { for room.opponents().map(|player| html! {
+                <Opponent key={player.id} player={player.clone()} />
+            }) }
+        </div>
+    }
+}
+
+/// Create or join a race room, or leave the current one
+#[function_component]
+pub fn RacePanel() -> Html {
+    let i18n = use_i18n();
+    let race = use_race();
+    let name = use_state(String::new);
+    let code = use_state(String::new);
+
+    let on_create = {
+        let race = race.clone();
+        let name = name.clone();
+        Callback::from(move |_| {
+            race.dispatch(RaceAction::Create {
+                name: (*name).clone(),
+            })
+        })
+    };
+    let on_join = {
+        let race = race.clone();
+        let name = name.clone();
+        let code = code.clone();
+        Callback::from(move |_| {
+            race.dispatch(RaceAction::Join {
+                code: (*code).clone(),
+                name: (*name).clone(),
+            })
+        })
+    };
+    let on_leave = {
+        let race = race.clone();
+        Callback::from(move |_| race.dispatch(RaceAction::Leave))
+    };
+
+    if let Some(room) = race.room() {
+        return html! {
+            <>
+                <div class="row subtitle">
+                    { i18n.t("race-code") }{ ": " }<b>{ &room.code }</b>
+                </div>
+                <div class="row subtitle">
+                    { i18n.tn("race-players", room.players.len()) }
+                </div>
+                <div class="row">
+                    <Opponents />
+                </div>
+                <div class="row share">
+                    <button onclick={on_leave}>{ i18n.t("race-leave") }</button>
+                </div>
+            </>
+        };
+    }
+
+    let connecting = race.status == RaceStatus::Connecting;
+    html! {
+        <>
+            <div class="row subtitle">{ i18n.t("race-intro") }</div>
+            <label class="setting">
+                <b>{ i18n.t("race-name") }</b>
+                <input
+                    value={(*name).clone()}
+                    maxlength="16"
+                    oninput={on_text(name.clone())} />
+            </label>
+            <div class="row share">
+                <button
+                    disabled={connecting || name.is_empty()}
+                    onclick={on_create}>
+                    { i18n.t("race-create") }
+                </button>
+            </div>
+            <label class="setting">
+                <b>{ i18n.t("race-code") }</b>
+                <input
+                    value={(*code).clone()}
+                    maxlength="5"
+                    oninput={on_text(code.clone())} />
+            </label>
+            <div class="row share">
+                <button
+                    disabled={connecting || name.is_empty() || code.is_empty()}
+                    onclick={on_join}>
+                    { i18n.t("race-join") }
+                </button>
+            </div>
+            if connecting {
+                <div class="row subtitle">{ i18n.t("loading") }</div>
+            }
+            if let RaceStatus::Failed(message) = &race.status {
+                <div class="row subtitle">
+                    { i18n.t("race-error") }{ ": " }{ message }
+                </div>
+            }
+        </>
+    }
+}
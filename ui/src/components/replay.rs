@@ -0,0 +1,143 @@
+use gloo::timers::callback::Timeout;
+use yew::{
+    classes, function_component, html, use_effect_with, use_state, Callback,
+    Html,
+};
+
+use crate::{
+    components::GuessView,
+    i18n::use_i18n,
+    state::{
+        use_game_state, use_modal, GameEvent, GameEventKind, Guess, ModalKind,
+    },
+};
+
+/// Bounds of the delay between replayed events in milliseconds.
+/// The recorded pace is kept, but long pauses are cut short.
+const MIN_DELAY: i64 = 150;
+const MAX_DELAY: i64 = 1200;
+
+/// The board after a number of events
+#[derive(Default)]
+struct Frame {
+    guesses: Vec<Guess>,
+    current: Guess,
+    /// Message key of a rejection by the last event
+    rejected: Option<String>,
+}
+
+impl Frame {
+    fn at(events: &[GameEvent]) -> Frame {
+        let mut frame = Frame::default();
+        for event in events {
+            frame.rejected = None;
+            match &event.kind {
+                GameEventKind::Typed(current) => {
+                    frame.current = current.clone()
+                }
+                GameEventKind::Submitted(guess) => {
+                    frame.guesses.push(guess.clone());
+                    frame.current = Guess::default();
+                }
+                GameEventKind::Rejected(_, reason) => {
+                    frame.rejected = Some(reason.clone())
+                }
+                GameEventKind::Undone(_) => {
+                    frame.guesses.pop();
+                }
+            }
+        }
+        frame
+    }
+}
+
+/// Re-plays the recorded events of the game, step by step
+#[function_component]
+pub fn Replay() -> Html {
+    let game = use_game_state();
+    let modal = use_modal();
+    let i18n = use_i18n();
+    let visible = modal.is_open(ModalKind::Replay);
+    let step = use_state(|| 0_usize);
+
+    // Start over whenever the replay is opened or shows another game
+    {
+        let step = step.clone();
+        let key = game.storage_key();
+        use_effect_with((visible, key), move |_| step.set(0));
+    }
+
+    // Advance to the next event after the recorded delay
+    {
+        let step = step.clone();
+        let events = game.events.clone();
+        use_effect_with((visible, *step), move |(visible, current)| {
+            let current = *current;
+            let timer = (*visible && current < events.len()).then(|| {
+                let delay = match current {
+                    0 => MIN_DELAY,
+                    n => (events[n].at - events[n - 1].at)
+                        .clamp(MIN_DELAY, MAX_DELAY),
+                };
+                Timeout::new(delay as u32, move || step.set(current + 1))
+            });
+            move || drop(timer)
+        });
+    }
+
+    if game.events.is_empty() {
+        return html! {
+            <div class="row subtitle">{ i18n.t("replay-empty") }</div>
+        };
+    }
+
+    // The game may have changed since the last step
+    let frame = Frame::at(&game.events[..(*step).min(game.events.len())]);
+    // Guesses which were taken back are scored like any other,
+    // without a solution their hints are those of the same word.
+    let hints = game.guess_hints();
+    let hints_of = |guess: &Guess| match game.solution.is_empty() {
+        false => Some(guess.hints(&game.solution)),
+        true => game
+            .guesses
+            .iter()
+            .position(|other| other == guess)
+            .and_then(|i| hints.get(i).cloned()),
+    };
+    let rows = frame
+        .guesses
+        .iter()
+        .map(|guess| {
+            html! { <GuessView guess={guess.clone()} hints={hints_of(guess)} /> }
+        })
+        .collect::<Html>();
+    let max_guesses = 6;
+    let used = frame.guesses.len() + 1;
+    let pad = (used..max_guesses)
+        .map(|_| html! { <GuessView /> })
+        .collect::<Html>();
+
+    let on_restart = {
+        let step = step.clone();
+        Callback::from(move |_| step.set(0))
+    };
+
+    html! {
+        <>
+            <div class={classes!("row", "replay-board", frame.rejected)}>
+                <div class="guesses" role="grid" aria-label={i18n.t("guesses")}>
+                    <div class="history">{ rows }</div>
+                    if frame.guesses.len() < max_guesses {
+                        <div class="current">
+                            <GuessView guess={frame.current} />
+                        </div>
+                    }
+                    <div class="padding">{ pad }</div>
+                </div>
+            </div>
+            <div class="row share">
+                <button onclick={on_restart}>{ i18n.t("replay-restart") }</button>
+            </div>
+        </>
+    }
+}
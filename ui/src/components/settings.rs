@@ -0,0 +1,167 @@
+use yew::{classes, function_component, html, Callback, Html, Properties};
+
+use crate::{
+    components::SyncSettings,
+    i18n::{use_i18n, I18n, Locale},
+    state::{
+        sync_available, use_game_state, use_settings_context, KeyboardLayout,
+        Language, Palette, Settings, SettingsContext, State as GameState,
+        Theme,
+    },
+};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct SettingOptionsProps<T: Clone + PartialEq + 'static> {
+    pub label: String,
+    pub options: Vec<(T, String)>,
+    pub value: T,
+    pub on_select: Callback<T>,
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+/// Render a setting as a row of buttons, one per option,
+/// with the active value highlighted.
+#[function_component]
+pub fn SettingOptions<T: Clone + PartialEq + 'static>(
+    props: &SettingOptionsProps<T>,
+) -> Html {
+    let SettingOptionsProps {
+        label,
+        options,
+        value,
+        on_select,
+        disabled,
+    } = props;
+
+    let buttons = options
+        .iter()
+        .map(|(option, name)| {
+            let selected = (option == value).then_some("selected");
+            let onclick = {
+                let option = option.clone();
+                let on_select = on_select.clone();
+                Callback::from(move |_| on_select.emit(option.clone()))
+            };
+            html! {
+                <button
+                    class={classes!(selected)}
+                    disabled={*disabled}
+                    {onclick}>
+                    { name }
+                </button>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="setting">
+            <b>{ label }</b>
+            <div class="options">
+                { buttons }
+            </div>
+        </div>
+    }
+}
+
+fn on_update<T: 'static>(
+    ctx: &SettingsContext,
+    f: fn(&mut Settings, T),
+) -> Callback<T> {
+    let ctx = ctx.clone();
+    Callback::from(move |value| ctx.update(|s| f(s, value)))
+}
+
+fn on_off(i18n: &I18n) -> Vec<(bool, String)> {
+    vec![(true, i18n.t("on")), (false, i18n.t("off"))]
+}
+
+#[function_component]
+pub fn SettingsPanel() -> Html {
+    let ctx = use_settings_context();
+    let game = use_game_state();
+    let i18n = use_i18n();
+    let Settings {
+        hard_mode,
+        speed_mode,
+        theme,
+        palette,
+        keyboard_layout,
+        animations,
+        language,
+        locale,
+        ..
+    } = ctx.settings.clone();
+
+    // Hard mode can not be toggled while a game is in progress.
+    let hard_mode_locked = game.state == GameState::Running && game.tries() > 0;
+
+    html! {
+        <>
+            <SettingOptions<Language>
+                label={i18n.t("setting-language")}
+                options={Language::all()
+                    .into_iter()
+                    .map(|l| (l.clone(), l.name()))
+                    .collect::<Vec<_>>()}
+                value={language}
+                on_select={on_update(&ctx, |s, v: Language| {
+                    s.keyboard_layout = v.keyboard_layout();
+                    s.language = v;
+                })} />
+            <SettingOptions<Option<Locale>>
+                label={i18n.t("setting-locale")}
+                options={std::iter::once((None, i18n.t("auto")))
+                    .chain(Locale::all()
+                        .into_iter()
+                        .map(|l| (Some(l.clone()), l.name())))
+                    .collect::<Vec<_>>()}
+                value={locale}
+                on_select={on_update(&ctx, |s, v| s.locale = v)} />
+            <SettingOptions<bool>
+                label={i18n.t("setting-hard-mode")}
+                options={on_off(&i18n)}
+                value={hard_mode}
+                disabled={hard_mode_locked}
+                on_select={on_update(&ctx, |s, v| s.hard_mode = v)} />
+            <SettingOptions<bool>
+                label={i18n.t("setting-speed-mode")}
+                options={on_off(&i18n)}
+                value={speed_mode}
+                on_select={on_update(&ctx, |s, v| s.speed_mode = v)} />
+            <SettingOptions<Theme>
+                label={i18n.t("setting-theme")}
+                options={vec![
+                    (Theme::System, i18n.t("theme-system")),
+                    (Theme::Dark, i18n.t("theme-dark")),
+                    (Theme::Light, i18n.t("theme-light")),
+                ]}
+                value={theme}
+                on_select={on_update(&ctx, |s, v| s.theme = v)} />
+            <SettingOptions<Palette>
+                label={i18n.t("setting-colors")}
+                options={vec![
+                    (Palette::Default, i18n.t("palette-default")),
+                    (Palette::HighContrast, i18n.t("palette-high-contrast")),
+                ]}
+                value={palette}
+                on_select={on_update(&ctx, |s, v| s.palette = v)} />
+            <SettingOptions<KeyboardLayout>
+                label={i18n.t("setting-keyboard")}
+                options={KeyboardLayout::all()
+                    .into_iter()
+                    .map(|l| (l.clone(), l.name()))
+                    .collect::<Vec<_>>()}
+                value={keyboard_layout}
+                on_select={on_update(&ctx, |s, v| s.keyboard_layout = v)} />
+            <SettingOptions<bool>
+                label={i18n.t("setting-animations")}
+                options={on_off(&i18n)}
+                value={animations}
+                on_select={on_update(&ctx, |s, v| s.animations = v)} />
+            if sync_available() {
+                <SyncSettings />
+            }
+        </>
+    }
+}
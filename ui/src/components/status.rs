@@ -0,0 +1,63 @@
+use yew::{function_component, html, Callback, Html, Properties};
+
+use crate::{
+    i18n::use_i18n,
+    state::{Resource, ResourceHandle},
+};
+
+/// Shown while the app is loading
+#[function_component]
+pub fn Splash() -> Html {
+    let i18n = use_i18n();
+    html! {
+        <div class="status splash" role="status" aria-live="polite">
+            <h1>{ "nerdle" }</h1>
+            <p>{ i18n.t("loading") }</p>
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ErrorScreenProps {
+    pub message: String,
+    #[prop_or_default]
+    pub on_retry: Option<Callback<()>>,
+}
+
+/// Shown when the app can't continue, with an optional retry
+#[function_component]
+pub fn ErrorScreen(props: &ErrorScreenProps) -> Html {
+    let ErrorScreenProps { message, on_retry } = props;
+    let i18n = use_i18n();
+    let retry = on_retry.as_ref().map(|on_retry| {
+        let onclick = on_retry.reform(|_| ());
+        html! {
+            <button onclick={onclick}>{ i18n.t("retry") }</button>
+        }
+    });
+    html! {
+        <div class="status error" role="alert">
+            <h1>{ i18n.t("error-title") }</h1>
+            <p>{ i18n.t("error-load") }</p>
+            <code>{ message }</code>
+            { for retry }
+        </div>
+    }
+}
+
+/// Render a resource: the splash while loading, the error
+/// screen with a retry button on failure, else `ready`.
+pub fn view_resource<T>(
+    resource: &ResourceHandle<T>,
+    ready: impl FnOnce(&T) -> Html,
+) -> Html {
+    match &resource.state {
+        Resource::Loading => html! { <Splash /> },
+        Resource::Failed(message) => html! {
+            <ErrorScreen
+                message={message.clone()}
+                on_retry={resource.retry.clone()} />
+        },
+        Resource::Ready(value) => ready(value),
+    }
+}
@@ -0,0 +1,56 @@
+use yew::{function_component, html, use_state, Callback, Html};
+
+use crate::{
+    api::clean_name,
+    components::on_text,
+    i18n::use_i18n,
+    state::{new_sync_code, use_settings_context, MIN_SYNC_CODE_LENGTH},
+};
+
+/// Share the games between devices with a sync code
+#[function_component]
+pub fn SyncSettings() -> Html {
+    let i18n = use_i18n();
+    let ctx = use_settings_context();
+    let code = use_state(String::new);
+
+    let set_code = |code: Option<String>| {
+        let ctx = ctx.clone();
+        Callback::from(move |_| {
+            let code = code.clone().unwrap_or_else(new_sync_code);
+            ctx.update(|settings| settings.sync_code = code)
+        })
+    };
+
+    if !ctx.settings.sync_code.is_empty() {
+        return html! {
+            <div class="setting">
+                <b>{ i18n.t("setting-sync") }</b>
+                <code>{ &ctx.settings.sync_code }</code>
+                <div class="options">
+                    <button onclick={set_code(Some(String::new()))}>
+                        { i18n.t("sync-stop") }
+                    </button>
+                </div>
+            </div>
+        };
+    }
+
+    let entered = clean_name(&code).to_lowercase();
+    let valid = entered.len() >= MIN_SYNC_CODE_LENGTH;
+    html! {
+        <div class="setting">
+            <b>{ i18n.t("setting-sync") }</b>
+            <input
+                value={(*code).clone()}
+                placeholder={i18n.t("sync-code")}
+                oninput={on_text(code.clone())} />
+            <div class="options">
+                <button disabled={!valid} onclick={set_code(Some(entered))}>
+                    { i18n.t("sync-use") }
+                </button>
+                <button onclick={set_code(None)}>{ i18n.t("sync-new") }</button>
+            </div>
+        </div>
+    }
+}
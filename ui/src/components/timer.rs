@@ -0,0 +1,38 @@
+use gloo::timers::callback::Interval;
+use yew::{function_component, html, use_effect_with, use_state, Html};
+
+use crate::{
+    i18n::use_i18n,
+    state::{format_time, now_millis, use_game_state},
+};
+
+/// Time since the first keystroke, ticking until the game ends
+#[function_component]
+pub fn Timer() -> Html {
+    let game = use_game_state();
+    let i18n = use_i18n();
+    let now = use_state(now_millis);
+
+    {
+        let now = now.clone();
+        let running = game.started_at.is_some() && game.finished_at.is_none();
+        use_effect_with(running, move |running| {
+            now.set(now_millis());
+            let interval = running
+                .then(|| Interval::new(250, move || now.set(now_millis())));
+            move || drop(interval)
+        });
+    }
+
+    let elapsed = match (game.started_at, game.finished_at) {
+        (Some(started), Some(finished)) => finished - started,
+        (Some(started), None) => *now - started,
+        _ => 0,
+    };
+
+    html! {
+        <div class="timer" role="timer" aria-label={i18n.t("time")}>
+            { format_time(elapsed) }
+        </div>
+    }
+}
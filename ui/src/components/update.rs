@@ -0,0 +1,129 @@
+use anyhow::anyhow;
+use gloo::{events::EventListener, utils::window};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    wasm_bindgen::{JsCast, JsValue},
+    ServiceWorker, ServiceWorkerRegistration, ServiceWorkerState,
+};
+use yew::{
+    function_component, html, use_effect_with, use_state, Callback, Html,
+    UseStateSetter,
+};
+
+use crate::{
+    api::{self, BASE_PATH},
+    i18n::use_i18n,
+    state::Language,
+    task::spawn,
+};
+
+/// Service workers need a secure context, and are left out
+/// of debug builds so they don't serve stale builds.
+fn service_worker_supported() -> bool {
+    let navigator = window().navigator();
+    !cfg!(debug_assertions)
+        && js_sys::Reflect::has(&navigator, &"serviceWorker".into())
+            .unwrap_or(false)
+}
+
+/// URL of the service worker, with the directories of the
+/// day files it keeps for offline play. Server mode keeps
+/// the solutions off the client, so there are none.
+fn worker_url() -> String {
+    let base = BASE_PATH.unwrap_or_default().trim_end_matches('/');
+    let data = match api::server_mode() {
+        true => vec![],
+        false => Language::all()
+            .iter()
+            .map(|language| language.data_path())
+            .collect(),
+    };
+    format!("{}/sw.js?data={}", base, data.join(","))
+}
+
+fn js_error(err: JsValue) -> anyhow::Error {
+    anyhow!("{:?}", err)
+}
+
+/// Register the service worker and report a new build once
+/// it is installed and waiting to take over.
+async fn register(
+    waiting: UseStateSetter<Option<ServiceWorker>>,
+) -> anyhow::Result<()> {
+    let container = window().navigator().service_worker();
+    let registration: ServiceWorkerRegistration =
+        JsFuture::from(container.register(&worker_url()))
+            .await
+            .map_err(js_error)?
+            .unchecked_into();
+
+    // Without a controller this is the first install,
+    // which has nothing to replace
+    let controlled = {
+        let container = container.clone();
+        move || container.controller().is_some()
+    };
+
+    // A build installed on an earlier visit
+    if let Some(worker) = registration.waiting() {
+        if controlled() {
+            waiting.set(Some(worker));
+        }
+    }
+
+    // The app lives as long as the page, and so do the listeners
+    let target = registration.clone();
+    EventListener::new(&target, "updatefound", move |_| {
+        let Some(worker) = registration.installing() else {
+            return;
+        };
+        let waiting = waiting.clone();
+        let controlled = controlled.clone();
+        let target = worker.clone();
+        EventListener::new(&target, "statechange", move |_| {
+            if worker.state() == ServiceWorkerState::Installed && controlled() {
+                waiting.set(Some(worker.clone()));
+            }
+        })
+        .forget();
+    })
+    .forget();
+    Ok(())
+}
+
+/// Offers to reload when a new build is available
+#[function_component]
+pub fn UpdatePrompt() -> Html {
+    let i18n = use_i18n();
+    let waiting = use_state(|| None::<ServiceWorker>);
+
+    {
+        let waiting = waiting.setter();
+        use_effect_with((), move |_| {
+            if service_worker_supported() {
+                spawn(register(waiting));
+            }
+        });
+    }
+
+    let Some(worker) = (*waiting).clone() else {
+        return html! {};
+    };
+
+    // Reload once the new worker took over
+    let on_reload = Callback::from(move |_| {
+        let container = window().navigator().service_worker();
+        EventListener::once(&container, "controllerchange", |_| {
+            let _ = window().location().reload();
+        })
+        .forget();
+        let _ = worker.post_message(&"skip-waiting".into());
+    });
+
+    html! {
+        <div class="update" role="status">
+            <span>{ i18n.t("update-available") }</span>
+            <button onclick={on_reload}>{ i18n.t("reload") }</button>
+        </div>
+    }
+}
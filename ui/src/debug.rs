@@ -1,12 +1,218 @@
+use std::{cell::Cell, fmt, panic};
+
+use gloo::{
+    storage::{LocalStorage, Storage},
+    utils::{document, window},
+};
+use serde::{Deserialize, Serialize};
 use web_sys::console;
 
-pub fn console_log(s: &str) {
-    console::log_1(&s.into());
+use crate::{
+    i18n::{I18n, Locale},
+    state::Settings,
+};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize,
+)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    pub fn parse(name: &str) -> Option<Level> {
+        match name.trim().to_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The most verbose level, set at build time with
+/// `NERDLE_LOG_LEVEL`. Defaults to debug for debug builds and
+/// to info for release builds.
+pub fn max_level() -> Level {
+    let default = match cfg!(debug_assertions) {
+        true => Level::Debug,
+        false => Level::Info,
+    };
+    option_env!("NERDLE_LOG_LEVEL")
+        .and_then(Level::parse)
+        .unwrap_or(default)
+}
+
+/// Endpoint receiving error reports, set at build time.
+/// Without it, nothing is reported.
+pub const REPORT_URL: Option<&str> = option_env!("NERDLE_REPORT_URL");
+
+/// Storage key of the runtime log level, e.g. set from the
+/// console with `localStorage.setItem("log-level", "\"warn\"")`
+const LEVEL_KEY: &str = "log-level";
+
+thread_local! {
+    static LEVEL: Cell<Option<Level>> = const { Cell::new(None) };
+}
+
+/// The current log level: the stored runtime level,
+/// capped to the maximum of the build.
+pub fn level() -> Level {
+    LEVEL.with(|level| {
+        let current = level.get().unwrap_or_else(|| {
+            let stored: Option<String> = LocalStorage::get(LEVEL_KEY).ok();
+            let stored = stored.as_deref().and_then(Level::parse);
+            let current = stored.unwrap_or(max_level()).min(max_level());
+            level.set(Some(current));
+            current
+        });
+        current
+    })
+}
+
+pub fn set_level(new: Level) {
+    let _ = LocalStorage::set(LEVEL_KEY, new.to_string());
+    LEVEL.with(|level| level.set(Some(new.min(max_level()))));
+}
+
+pub fn enabled(level: Level) -> bool {
+    level <= self::level()
+}
+
+pub fn console_log(level: Level, s: &str) {
+    if !enabled(level) {
+        return;
+    }
+    let s = s.into();
+    match level {
+        Level::Error => console::error_1(&s),
+        Level::Warn => console::warn_1(&s),
+        Level::Info => console::info_1(&s),
+        Level::Debug => console::debug_1(&s),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ErrorReport {
+    pub level: Level,
+    pub message: String,
+    /// Page the error happened on
+    pub url: String,
+    pub user_agent: String,
+    pub timestamp: String,
+}
+
+impl ErrorReport {
+    pub fn new(level: Level, message: &str) -> Self {
+        ErrorReport {
+            level,
+            message: message.into(),
+            url: window().location().href().unwrap_or_default(),
+            user_agent: window().navigator().user_agent().unwrap_or_default(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Send an error report to the report endpoint, if any.
+/// The report is sent as a beacon, so it is delivered even
+/// if the page is about to go away or the app has crashed.
+pub fn report(level: Level, message: &str) {
+    let Some(url) = REPORT_URL else {
+        return;
+    };
+    let report = ErrorReport::new(level, message);
+    if let Ok(body) = serde_json::to_string(&report) {
+        let _ = window()
+            .navigator()
+            .send_beacon_with_opt_str(url, Some(&body));
+    }
+}
+
+/// Replace the page with an error screen. Used when the app
+/// can't render anything anymore.
+fn show_crash_screen() {
+    let locale = Settings::load().locale.unwrap_or_else(Locale::detect);
+    let i18n = I18n::new(locale);
+    let html = format!(
+        "<div class=\"status error\" role=\"alert\">\
+            <h1>{}</h1><p>{}</p>\
+            <button onclick=\"location.reload()\">{}</button>\
+        </div>",
+        i18n.t("error-title"),
+        i18n.t("error-crash"),
+        i18n.t("reload"),
+    );
+    if let Some(body) = document().body() {
+        body.set_inner_html(&html);
+    }
+}
+
+/// Log and report panics and show an error screen
+/// instead of a blank or frozen page.
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        let message = info.to_string();
+        console_log(Level::Error, &message);
+        report(Level::Error, &message);
+        show_crash_screen();
+    }));
+}
+
+#[macro_export]
+macro_rules! log_at {
+    ($level:expr, $($t:tt)*) => {
+        if $crate::debug::enabled($level) {
+            $crate::debug::console_log($level, &format!($($t)*))
+        }
+    }
+}
+
+/// Log an error and report it
+#[macro_export]
+macro_rules! log_error {
+    ($($t:tt)*) => {{
+        let message = format!($($t)*);
+        $crate::debug::console_log($crate::debug::Level::Error, &message);
+        $crate::debug::report($crate::debug::Level::Error, &message);
+    }}
+}
+
+#[macro_export]
+macro_rules! log_warn {
+    ($($t:tt)*) => ($crate::log_at!($crate::debug::Level::Warn, $($t)*))
+}
+
+#[macro_export]
+macro_rules! log_info {
+    ($($t:tt)*) => ($crate::log_at!($crate::debug::Level::Info, $($t)*))
+}
+
+#[macro_export]
+macro_rules! log_debug {
+    ($($t:tt)*) => ($crate::log_at!($crate::debug::Level::Debug, $($t)*))
 }
 
+/// Log at info level
 #[macro_export]
 macro_rules! log {
-    ($($t:tt)*) => (crate::debug::console_log(&format!($($t)*)))
+    ($($t:tt)*) => ($crate::log_info!($($t)*))
 }
 
-pub use log;
+pub use {log, log_at, log_debug, log_error, log_info, log_warn};
@@ -0,0 +1,116 @@
+//! Compact binary encoding of word lists.
+//!
+//! The words are sorted and front coded: every word stores
+//! the number of bytes it shares with the previous word and
+//! the remaining bytes. Five letter words mostly shrink to
+//! a third of their text. The module only depends on std,
+//! as the build script uses it to embed the word lists.
+
+const MAGIC: &[u8; 4] = b"NWL1";
+
+/// Version of a word list: the FNV-1a hash of its text
+pub fn content_hash(text: &str) -> String {
+    let hash = text.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Encode the non-empty lines of a word list
+pub fn encode(text: &str) -> Vec<u8> {
+    let mut words: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.sort_unstable();
+    words.dedup();
+
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend((words.len() as u32).to_le_bytes());
+    let mut previous = "";
+    for word in words {
+        // Share whole characters only, so both parts stay UTF-8
+        let shared: usize = previous
+            .chars()
+            .zip(word.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        let suffix = &word.as_bytes()[shared..];
+        bytes.push(u8::try_from(shared).expect("word too long"));
+        bytes.push(u8::try_from(suffix.len()).expect("word too long"));
+        bytes.extend(suffix);
+        previous = word;
+    }
+    bytes
+}
+
+/// Decode an encoded word list, `None` if it is corrupt
+pub fn decode(bytes: &[u8]) -> Option<Vec<String>> {
+    let rest = bytes.strip_prefix(MAGIC)?;
+    let count = u32::from_le_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+    let mut rest = &rest[4..];
+    // Every word takes at least two bytes, whatever the count claims
+    let mut words: Vec<String> = Vec::with_capacity(count.min(rest.len() / 2));
+    for _ in 0..count {
+        let (shared, len) = (*rest.first()? as usize, *rest.get(1)? as usize);
+        let suffix = std::str::from_utf8(rest.get(2..2 + len)?).ok()?;
+        let previous = words.last().map(String::as_str).unwrap_or("");
+        let word = previous.get(..shared)?.to_string() + suffix;
+        words.push(word);
+        rest = &rest[2 + len..];
+    }
+    rest.is_empty().then_some(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(text: &str) -> Vec<String> {
+        decode(&encode(text)).expect("valid encoding")
+    }
+
+    #[test]
+    fn decodes_the_sorted_words() {
+        assert_eq!(round_trip(""), Vec::<String>::new());
+        assert_eq!(round_trip("\n  \n"), Vec::<String>::new());
+        assert_eq!(
+            round_trip("slate\ncrane\ncrate\ncrane\ncr\n"),
+            ["cr", "crane", "crate", "slate"]
+        );
+        // é and è share their first byte, but not a character
+        assert_eq!(
+            round_trip("ñu\nèa\néa\nñame\näpfel\näpfeln\n"),
+            ["äpfel", "äpfeln", "èa", "éa", "ñame", "ñu"]
+        );
+    }
+
+    #[test]
+    fn rejects_corrupt_input() {
+        let bytes = encode("crane\ncrate\nñame\nñu\n");
+        for end in 0..bytes.len() {
+            assert_eq!(decode(&bytes[..end]), None, "truncated at {}", end);
+        }
+        assert_eq!(decode(&[bytes.as_slice(), b"x"].concat()), None);
+        assert_eq!(decode(&[b"NWL0", &bytes[4..]].concat()), None);
+
+        // A count far beyond the data
+        let mut huge = bytes.clone();
+        huge[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(decode(&huge), None);
+
+        // Sharing half of the ñ of the previous word
+        let mut split = MAGIC.to_vec();
+        split.extend(2_u32.to_le_bytes());
+        split.extend([0, 2, 0xc3, 0xb1, 1, 1, b'a']);
+        assert_eq!(decode(&split), None);
+
+        // A suffix which is not UTF-8
+        let mut invalid = MAGIC.to_vec();
+        invalid.extend(1_u32.to_le_bytes());
+        invalid.extend([0, 1, 0xff]);
+        assert_eq!(decode(&invalid), None);
+    }
+}
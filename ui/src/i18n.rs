@@ -0,0 +1,495 @@
+use serde::{Deserialize, Serialize};
+use yew::hook;
+
+use crate::state::use_settings;
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+    French,
+}
+
+impl Locale {
+    pub fn all() -> Vec<Locale> {
+        vec![Locale::English, Locale::German, Locale::French]
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Locale::English => "English",
+            Locale::German => "Deutsch",
+            Locale::French => "Français",
+        }
+        .into()
+    }
+
+    /// Parse a language tag like `de-DE`
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let lang = tag.split(['-', '_']).next()?.to_lowercase();
+        match lang.as_str() {
+            "en" => Some(Locale::English),
+            "de" => Some(Locale::German),
+            "fr" => Some(Locale::French),
+            _ => None,
+        }
+    }
+
+    /// Detect the locale from `navigator.language`
+    pub fn detect() -> Locale {
+        web_sys::window()
+            .and_then(|window| window.navigator().language())
+            .and_then(|tag| Locale::from_tag(&tag))
+            .unwrap_or_default()
+    }
+}
+
+/// Plural categories; all supported locales
+/// only distinguish between one and other.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Plural {
+    One,
+    Other,
+}
+
+impl Plural {
+    pub fn for_count(locale: &Locale, n: usize) -> Plural {
+        let one = match locale {
+            Locale::English | Locale::German => n == 1,
+            Locale::French => n <= 1,
+        };
+        match one {
+            true => Plural::One,
+            false => Plural::Other,
+        }
+    }
+}
+
+type Catalogue = &'static [(&'static str, &'static str)];
+
+const EN: Catalogue = &[
+    ("loading", "Loading..."),
+    ("error-title", "Something went wrong"),
+    ("error-load", "The puzzle of today could not be loaded."),
+    ("retry", "Try again"),
+    (
+        "error-crash",
+        "The game crashed. Reloading the page should help.",
+    ),
+    ("reload", "Reload"),
+    ("update-available", "A new version is available."),
+    ("statistics", "Statistics"),
+    ("settings", "Settings"),
+    ("help", "Help"),
+    ("help-title", "How to play"),
+    ("help-goal", "Guess the word in 6 tries."),
+    ("help-rule-word", "Each guess must be a valid word."),
+    (
+        "help-rule-hints",
+        "The color of the tiles shows how close your guess was.",
+    ),
+    ("help-examples", "Examples"),
+    (
+        "help-example-correct",
+        "{letter} is in the word and in the correct spot.",
+    ),
+    (
+        "help-example-misplaced",
+        "{letter} is in the word but in the wrong spot.",
+    ),
+    (
+        "help-example-incorrect",
+        "{letter} is not in the word in any spot.",
+    ),
+    ("close", "Close"),
+    ("title-win", "Congratulations!"),
+    ("title-loss", "You lost!"),
+    ("title-running", "Good luck!"),
+    ("solved-in.one", "Solved in {n} try"),
+    ("solved-in.other", "Solved in {n} tries"),
+    ("played", "Played"),
+    ("current-streak", "Current Streak"),
+    ("max-streak", "Max Streak"),
+    ("setting-speed-mode", "Speed Mode"),
+    ("time", "Time"),
+    ("fastest", "Fastest games"),
+    ("share", "Share"),
+    ("replay", "Replay"),
+    ("replay-empty", "No moves were recorded for this game."),
+    ("replay-restart", "Start over"),
+    ("race", "Race"),
+    ("race-intro", "Race your friends on the same word."),
+    ("race-name", "Name"),
+    ("race-code", "Room"),
+    ("race-create", "New room"),
+    ("race-join", "Join"),
+    ("race-leave", "Leave"),
+    ("race-error", "Race failed"),
+    ("race-players.one", "{n} player"),
+    ("race-players.other", "{n} players"),
+    ("race-tries.one", "{n} try"),
+    ("race-tries.other", "{n} tries"),
+    ("race-solved", "Solved"),
+    ("race-failed", "Out of tries"),
+    ("challenge", "Challenge"),
+    ("challenge-intro", "Pick a word and send the link to a friend."),
+    ("challenge-word", "Word"),
+    ("challenge-create", "Create link"),
+    ("challenge-copy", "Copy link"),
+    ("challenge-playing", "You are playing a challenge of a friend."),
+    ("challenge-leave", "Play today's puzzle"),
+    ("error-empty", "Enter a word"),
+    ("leaderboard", "Leaderboard"),
+    ("leaderboard-intro", "Compare your daily results with a group. Only your nickname and results are shared."),
+    ("leaderboard-nickname", "Nickname"),
+    ("leaderboard-group", "Group"),
+    ("leaderboard-join", "Join"),
+    ("leaderboard-leave", "Leave group"),
+    ("leaderboard-today", "Today"),
+    ("leaderboard-all-time", "All time"),
+    ("undo", "Undo"),
+    ("today", "Today"),
+    ("practice", "Practice"),
+    ("previous-day", "‹ Previous day"),
+    ("next-day", "Next day ›"),
+    ("new-word", "New word"),
+    ("reset-today", "Reset today"),
+    ("copied", "Copied to clipboard!"),
+    ("setting-language", "Language"),
+    ("setting-locale", "Interface"),
+    ("setting-hard-mode", "Hard Mode"),
+    ("setting-theme", "Theme"),
+    ("setting-colors", "Colors"),
+    ("setting-keyboard", "Keyboard"),
+    ("setting-animations", "Animations"),
+    ("setting-sync", "Sync"),
+    ("sync-code", "Code of another device"),
+    ("sync-use", "Use code"),
+    ("sync-new", "New code"),
+    ("sync-stop", "Stop"),
+    ("auto", "Auto"),
+    ("on", "On"),
+    ("off", "Off"),
+    ("theme-system", "System"),
+    ("theme-dark", "Dark"),
+    ("theme-light", "Light"),
+    ("palette-default", "Default"),
+    ("palette-high-contrast", "High Contrast"),
+    ("guesses", "Guesses"),
+    ("keyboard", "Keyboard"),
+    ("empty", "empty"),
+    ("hint-correct", "correct"),
+    ("hint-misplaced", "present"),
+    ("hint-incorrect", "absent"),
+    ("key-enter", "Enter"),
+    ("key-bksp", "Backspace"),
+    ("error-too-short", "Not enough letters"),
+    ("error-not-a-word", "Not in word list"),
+    ("error-hard-mode", "Hard mode: use all revealed hints"),
+];
+
+const DE: Catalogue = &[
+    ("loading", "Lädt..."),
+    ("error-title", "Etwas ist schiefgelaufen"),
+    (
+        "error-load",
+        "Das Rätsel von heute konnte nicht geladen werden.",
+    ),
+    ("retry", "Erneut versuchen"),
+    (
+        "error-crash",
+        "Das Spiel ist abgestürzt. Ein Neuladen der Seite sollte helfen.",
+    ),
+    ("reload", "Neu laden"),
+    ("update-available", "Eine neue Version ist verfügbar."),
+    ("statistics", "Statistik"),
+    ("settings", "Einstellungen"),
+    ("help", "Hilfe"),
+    ("help-title", "So wird gespielt"),
+    ("help-goal", "Errate das Wort in 6 Versuchen."),
+    (
+        "help-rule-word",
+        "Jeder Versuch muss ein gültiges Wort sein.",
+    ),
+    (
+        "help-rule-hints",
+        "Die Farbe der Felder zeigt, wie nah du dran warst.",
+    ),
+    ("help-examples", "Beispiele"),
+    (
+        "help-example-correct",
+        "{letter} ist im Wort und an der richtigen Stelle.",
+    ),
+    (
+        "help-example-misplaced",
+        "{letter} ist im Wort, aber an der falschen Stelle.",
+    ),
+    (
+        "help-example-incorrect",
+        "{letter} kommt im Wort nicht vor.",
+    ),
+    ("close", "Schließen"),
+    ("title-win", "Glückwunsch!"),
+    ("title-loss", "Leider verloren!"),
+    ("title-running", "Viel Glück!"),
+    ("solved-in.one", "Gelöst mit {n} Versuch"),
+    ("solved-in.other", "Gelöst mit {n} Versuchen"),
+    ("played", "Gespielt"),
+    ("current-streak", "Aktuelle Serie"),
+    ("max-streak", "Längste Serie"),
+    ("setting-speed-mode", "Speedmodus"),
+    ("time", "Zeit"),
+    ("fastest", "Schnellste Spiele"),
+    ("share", "Teilen"),
+    ("replay", "Wiederholung"),
+    (
+        "replay-empty",
+        "Für dieses Spiel wurden keine Züge aufgezeichnet.",
+    ),
+    ("replay-restart", "Von vorne"),
+    ("race", "Rennen"),
+    ("race-intro", "Tritt gegen Freunde mit demselben Wort an."),
+    ("race-name", "Name"),
+    ("race-code", "Raum"),
+    ("race-create", "Neuer Raum"),
+    ("race-join", "Beitreten"),
+    ("race-leave", "Verlassen"),
+    ("race-error", "Rennen fehlgeschlagen"),
+    ("race-players.one", "{n} Spieler"),
+    ("race-players.other", "{n} Spieler"),
+    ("race-tries.one", "{n} Versuch"),
+    ("race-tries.other", "{n} Versuche"),
+    ("race-solved", "Gelöst"),
+    ("race-failed", "Keine Versuche mehr"),
+    ("challenge", "Duell"),
+    ("challenge-intro", "Wähle ein Wort und schicke den Link an Freunde."),
+    ("challenge-word", "Wort"),
+    ("challenge-create", "Link erstellen"),
+    ("challenge-copy", "Link kopieren"),
+    ("challenge-playing", "Du spielst ein Duell von Freunden."),
+    ("challenge-leave", "Rätsel des Tages spielen"),
+    ("error-empty", "Gib ein Wort ein"),
+    ("leaderboard", "Bestenliste"),
+    ("leaderboard-intro", "Vergleiche deine Ergebnisse mit einer Gruppe. Geteilt werden nur dein Spitzname und deine Ergebnisse."),
+    ("leaderboard-nickname", "Spitzname"),
+    ("leaderboard-group", "Gruppe"),
+    ("leaderboard-join", "Beitreten"),
+    ("leaderboard-leave", "Gruppe verlassen"),
+    ("leaderboard-today", "Heute"),
+    ("leaderboard-all-time", "Insgesamt"),
+    ("undo", "Rückgängig"),
+    ("today", "Heute"),
+    ("practice", "Training"),
+    ("previous-day", "‹ Vortag"),
+    ("next-day", "Folgetag ›"),
+    ("new-word", "Neues Wort"),
+    ("reset-today", "Heute zurücksetzen"),
+    ("copied", "In die Zwischenablage kopiert!"),
+    ("setting-language", "Sprache"),
+    ("setting-locale", "Oberfläche"),
+    ("setting-hard-mode", "Schwerer Modus"),
+    ("setting-theme", "Design"),
+    ("setting-colors", "Farben"),
+    ("setting-keyboard", "Tastatur"),
+    ("setting-animations", "Animationen"),
+    ("setting-sync", "Abgleich"),
+    ("sync-code", "Code eines anderen Geräts"),
+    ("sync-use", "Code verwenden"),
+    ("sync-new", "Neuer Code"),
+    ("sync-stop", "Beenden"),
+    ("auto", "Automatisch"),
+    ("on", "An"),
+    ("off", "Aus"),
+    ("theme-system", "System"),
+    ("theme-dark", "Dunkel"),
+    ("theme-light", "Hell"),
+    ("palette-default", "Standard"),
+    ("palette-high-contrast", "Hoher Kontrast"),
+    ("guesses", "Versuche"),
+    ("keyboard", "Tastatur"),
+    ("empty", "leer"),
+    ("hint-correct", "richtig"),
+    ("hint-misplaced", "an falscher Stelle"),
+    ("hint-incorrect", "nicht enthalten"),
+    ("key-enter", "Eingabe"),
+    ("key-bksp", "Löschen"),
+    ("error-too-short", "Zu wenige Buchstaben"),
+    ("error-not-a-word", "Kein Wort aus der Liste"),
+    ("error-hard-mode", "Schwerer Modus: alle Hinweise verwenden"),
+];
+
+const FR: Catalogue = &[
+    ("loading", "Chargement..."),
+    ("error-title", "Une erreur est survenue"),
+    ("error-load", "Le puzzle du jour n'a pas pu être chargé."),
+    ("retry", "Réessayer"),
+    (
+        "error-crash",
+        "Le jeu a planté. Recharger la page devrait aider.",
+    ),
+    ("reload", "Recharger"),
+    ("update-available", "Une nouvelle version est disponible."),
+    ("statistics", "Statistiques"),
+    ("settings", "Paramètres"),
+    ("help", "Aide"),
+    ("help-title", "Comment jouer"),
+    ("help-goal", "Devinez le mot en 6 essais."),
+    ("help-rule-word", "Chaque essai doit être un mot valide."),
+    (
+        "help-rule-hints",
+        "La couleur des cases indique si vous êtes proche.",
+    ),
+    ("help-examples", "Exemples"),
+    (
+        "help-example-correct",
+        "{letter} est dans le mot et bien placée.",
+    ),
+    (
+        "help-example-misplaced",
+        "{letter} est dans le mot mais mal placée.",
+    ),
+    ("help-example-incorrect", "{letter} n'est pas dans le mot."),
+    ("close", "Fermer"),
+    ("title-win", "Félicitations !"),
+    ("title-loss", "Perdu !"),
+    ("title-running", "Bonne chance !"),
+    ("solved-in.one", "Résolu en {n} essai"),
+    ("solved-in.other", "Résolu en {n} essais"),
+    ("played", "Parties"),
+    ("current-streak", "Série actuelle"),
+    ("max-streak", "Meilleure série"),
+    ("setting-speed-mode", "Mode rapide"),
+    ("time", "Temps"),
+    ("fastest", "Parties les plus rapides"),
+    ("share", "Partager"),
+    ("replay", "Revoir"),
+    (
+        "replay-empty",
+        "Aucun coup n'a été enregistré pour cette partie.",
+    ),
+    ("replay-restart", "Recommencer"),
+    ("race", "Course"),
+    ("race-intro", "Affrontez vos amis sur le même mot."),
+    ("race-name", "Nom"),
+    ("race-code", "Salle"),
+    ("race-create", "Nouvelle salle"),
+    ("race-join", "Rejoindre"),
+    ("race-leave", "Quitter"),
+    ("race-error", "Échec de la course"),
+    ("race-players.one", "{n} joueur"),
+    ("race-players.other", "{n} joueurs"),
+    ("race-tries.one", "{n} essai"),
+    ("race-tries.other", "{n} essais"),
+    ("race-solved", "Résolu"),
+    ("race-failed", "Plus d'essais"),
+    ("challenge", "Défi"),
+    ("challenge-intro", "Choisissez un mot et envoyez le lien à un ami."),
+    ("challenge-word", "Mot"),
+    ("challenge-create", "Créer le lien"),
+    ("challenge-copy", "Copier le lien"),
+    ("challenge-playing", "Vous jouez le défi d'un ami."),
+    ("challenge-leave", "Jouer le mot du jour"),
+    ("error-empty", "Saisissez un mot"),
+    ("leaderboard", "Classement"),
+    ("leaderboard-intro", "Comparez vos résultats avec un groupe. Seuls votre pseudo et vos résultats sont partagés."),
+    ("leaderboard-nickname", "Pseudo"),
+    ("leaderboard-group", "Groupe"),
+    ("leaderboard-join", "Rejoindre"),
+    ("leaderboard-leave", "Quitter le groupe"),
+    ("leaderboard-today", "Aujourd'hui"),
+    ("leaderboard-all-time", "Depuis toujours"),
+    ("undo", "Annuler"),
+    ("today", "Aujourd'hui"),
+    ("practice", "Entraînement"),
+    ("previous-day", "‹ Jour précédent"),
+    ("next-day", "Jour suivant ›"),
+    ("new-word", "Nouveau mot"),
+    ("reset-today", "Réinitialiser aujourd'hui"),
+    ("copied", "Copié dans le presse-papiers !"),
+    ("setting-language", "Langue"),
+    ("setting-locale", "Interface"),
+    ("setting-hard-mode", "Mode difficile"),
+    ("setting-theme", "Thème"),
+    ("setting-colors", "Couleurs"),
+    ("setting-keyboard", "Clavier"),
+    ("setting-animations", "Animations"),
+    ("setting-sync", "Synchronisation"),
+    ("sync-code", "Code d'un autre appareil"),
+    ("sync-use", "Utiliser le code"),
+    ("sync-new", "Nouveau code"),
+    ("sync-stop", "Arrêter"),
+    ("auto", "Auto"),
+    ("on", "Oui"),
+    ("off", "Non"),
+    ("theme-system", "Système"),
+    ("theme-dark", "Sombre"),
+    ("theme-light", "Clair"),
+    ("palette-default", "Standard"),
+    ("palette-high-contrast", "Contraste élevé"),
+    ("guesses", "Essais"),
+    ("keyboard", "Clavier"),
+    ("empty", "vide"),
+    ("hint-correct", "bien placée"),
+    ("hint-misplaced", "mal placée"),
+    ("hint-incorrect", "absente"),
+    ("key-enter", "Entrée"),
+    ("key-bksp", "Effacer"),
+    ("error-too-short", "Pas assez de lettres"),
+    ("error-not-a-word", "Mot inconnu"),
+    ("error-hard-mode", "Mode difficile : utilisez les indices"),
+];
+
+fn catalogue(locale: &Locale) -> Catalogue {
+    match locale {
+        Locale::English => EN,
+        Locale::German => DE,
+        Locale::French => FR,
+    }
+}
+
+fn lookup(catalogue: Catalogue, key: &str) -> Option<&'static str> {
+    catalogue
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, message)| *message)
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct I18n {
+    pub locale: Locale,
+}
+
+impl I18n {
+    pub fn new(locale: Locale) -> Self {
+        Self { locale }
+    }
+
+    /// Translate a message. Missing messages fall back
+    /// to english and finally to the key itself.
+    pub fn t(&self, key: &str) -> String {
+        lookup(catalogue(&self.locale), key)
+            .or_else(|| lookup(EN, key))
+            .unwrap_or(key)
+            .to_string()
+    }
+
+    /// Translate a message with a count, selecting
+    /// the plural form and substituting `{n}`.
+    pub fn tn(&self, key: &str, n: usize) -> String {
+        let form = match Plural::for_count(&self.locale, n) {
+            Plural::One => "one",
+            Plural::Other => "other",
+        };
+        self.t(&format!("{}.{}", key, form))
+            .replace("{n}", &n.to_string())
+    }
+}
+
+/// Get the translations for the configured locale, or
+/// the locale of the browser if none is set.
+#[hook]
+pub fn use_i18n() -> I18n {
+    let locale = use_settings().locale.unwrap_or_else(Locale::detect);
+    I18n::new(locale)
+}
@@ -1,5 +1,8 @@
 pub mod api;
 pub mod components;
 pub mod debug;
+pub mod encoding;
+pub mod i18n;
 pub mod state;
 pub mod task;
+pub mod validate;
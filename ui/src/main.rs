@@ -1,23 +1,37 @@
 use yew::{functional::function_component, html, Html};
 
 use nerdle_ui::{
-    components::Game,
-    state::{ConfigProvider, GameStateProvider, WordlistProvider},
+    components::{Game, UpdatePrompt},
+    debug::install_panic_hook,
+    state::{
+        ConfigProvider, GameStateProvider, ModalProvider, RaceProvider,
+        RouteProvider, SettingsProvider, WordlistProvider,
+    },
 };
 
 #[function_component]
 fn App() -> Html {
     html! {
+        <RouteProvider>
+        <SettingsProvider>
+        <UpdatePrompt />
         <ConfigProvider>
         <WordlistProvider>
         <GameStateProvider>
+        <RaceProvider>
+        <ModalProvider>
             <Game />
+        </ModalProvider>
+        </RaceProvider>
         </GameStateProvider>
         </WordlistProvider>
         </ConfigProvider>
+        </SettingsProvider>
+        </RouteProvider>
     }
 }
 
 fn main() {
+    install_panic_hook();
     yew::Renderer::<App>::new().render();
 }
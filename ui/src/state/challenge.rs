@@ -0,0 +1,223 @@
+use std::ops::RangeInclusive;
+
+use gloo::utils::window;
+use yew::{hook, use_memo};
+
+use crate::state::{
+    content_hash, normalize, use_route, use_settings, Language, Route, Wordlist,
+};
+
+/// Namespace of challenge games, kept apart from the daily stats
+pub const CHALLENGE_NAMESPACE: &str = "challenge";
+
+/// Numbers of letters a challenge can have
+const WORD_LENGTHS: RangeInclusive<usize> = 4..=7;
+
+/// Scrambles the word of a link, so it can't be read at a glance.
+/// This is no secret, only a spoiler guard.
+const KEY: &[u8] = b"nerdle";
+
+/// A puzzle with a word picked by a player, shared as a link
+#[derive(Debug, Clone, PartialEq)]
+pub struct Challenge {
+    pub language: Language,
+    pub word: String,
+}
+
+impl Challenge {
+    /// Check that the word can be played. Returns the message
+    /// key of the problem otherwise.
+    pub fn new(
+        language: &Language,
+        word: &str,
+        wordlist: &Wordlist,
+    ) -> Result<Self, &'static str> {
+        let word = normalize(word);
+        if word.is_empty() {
+            return Err("error-empty");
+        }
+        if !wordlist.contains(&word) {
+            return Err("error-not-a-word");
+        }
+        Ok(Challenge {
+            language: language.clone(),
+            word,
+        })
+    }
+
+    /// Encode as the scrambled hex digits of `<language>:<word>`,
+    /// followed by a check byte.
+    pub fn encode(&self) -> String {
+        let text = format!("{}:{}", self.language.code(), self.word);
+        let mut bytes: Vec<u8> = scramble(text.as_bytes());
+        bytes.push(checksum(&bytes));
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// Decode a code of [`Challenge::encode`], or `None` if
+    /// it is broken or the word can't be played.
+    pub fn decode(code: &str) -> Option<Self> {
+        if !code.len().is_multiple_of(2) || !code.is_ascii() {
+            return None;
+        }
+        let mut bytes = (0..code.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&code[i..i + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let check = bytes.pop()?;
+        if checksum(&bytes) != check {
+            return None;
+        }
+        let text = String::from_utf8(scramble(&bytes)).ok()?;
+        let (code, word) = text.split_once(':')?;
+        let word = normalize(word);
+        if !WORD_LENGTHS.contains(&word.chars().count()) {
+            return None;
+        }
+        Some(Challenge {
+            language: Language::from_code(code)?,
+            word,
+        })
+    }
+
+    /// The challenge of a route, if its link is intact and the
+    /// word is in the word list of its language
+    pub fn from_route(route: &Route) -> Option<Self> {
+        let Route::Challenge(code) = route else {
+            return None;
+        };
+        let challenge = Challenge::decode(code)?;
+        Wordlist::embedded(&challenge.language)
+            .contains(&challenge.word)
+            .then_some(challenge)
+    }
+
+    /// Stable id of the game, never 0 so the game is stored
+    pub fn id(&self) -> u32 {
+        let hash = content_hash(&self.encode());
+        u32::from_str_radix(&hash[..8], 16)
+            .unwrap_or_default()
+            .max(1)
+    }
+
+    /// Short name to compare the results of a challenge
+    pub fn tag(&self) -> String {
+        format!("{:08x}", self.id())
+    }
+
+    pub fn solution(&self) -> String {
+        self.language.fold(&self.word)
+    }
+
+    /// Link to the challenge on this site
+    pub fn url(&self) -> String {
+        let origin = window().location().origin().unwrap_or_default();
+        let route = Route::Challenge(self.encode());
+        format!("{}{}", origin, route.to_path())
+    }
+}
+
+/// The challenge played on the current route
+#[hook]
+pub fn use_challenge() -> Option<Challenge> {
+    let route = use_route();
+    (*use_memo(route, Challenge::from_route)).clone()
+}
+
+/// Language of the puzzle being played. A challenge comes
+/// in its own, without changing the one of the settings.
+#[hook]
+pub fn use_language() -> Language {
+    let challenge = use_challenge();
+    let language = use_settings().language;
+    challenge.map_or(language, |challenge| challenge.language)
+}
+
+fn scramble(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .zip(KEY.iter().cycle())
+        .enumerate()
+        .map(|(i, (byte, key))| byte ^ key ^ (i as u8).wrapping_mul(31))
+        .collect()
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0x5a_u8, |sum, byte| sum.rotate_left(3) ^ byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wordlist() -> Wordlist {
+        Wordlist::new(vec!["CRANE".into(), "ÄHREN".into()])
+    }
+
+    #[test]
+    fn accepts_only_words_of_the_list() {
+        let wordlist = wordlist();
+        let challenge = Challenge::new(&Language::English, "crane", &wordlist);
+        assert_eq!(challenge.unwrap().word, "CRANE");
+        assert_eq!(
+            Challenge::new(&Language::English, "xxxxx", &wordlist),
+            Err("error-not-a-word")
+        );
+        assert_eq!(
+            Challenge::new(&Language::English, " ", &wordlist),
+            Err("error-empty")
+        );
+    }
+
+    #[test]
+    fn round_trips_through_the_code() {
+        let challenge =
+            Challenge::new(&Language::German, "ähren", &wordlist()).unwrap();
+        let code = challenge.encode();
+        assert!(!code.to_uppercase().contains("HREN"));
+        assert_eq!(Challenge::decode(&code), Some(challenge));
+    }
+
+    #[test]
+    fn rejects_broken_codes() {
+        let challenge =
+            Challenge::new(&Language::English, "crane", &wordlist()).unwrap();
+        let code = challenge.encode();
+        assert_eq!(Challenge::decode(&code[1..]), None);
+        assert_eq!(Challenge::decode(&code.replace('a', "b")), None);
+        assert_eq!(Challenge::decode("zz"), None);
+        assert_eq!(Challenge::decode(""), None);
+    }
+
+    #[test]
+    fn rejects_words_which_can_not_be_played() {
+        let encode = |word: &str| {
+            let challenge = Challenge {
+                language: Language::English,
+                word: word.into(),
+            };
+            challenge.encode()
+        };
+        for word in ["", "CAT", "ABSTRACTS"] {
+            assert_eq!(Challenge::decode(&encode(word)), None, "{:?}", word);
+        }
+        let route = |word: &str| Route::Challenge(encode(word));
+        assert_eq!(Challenge::from_route(&route("ZZZZZ")), None);
+        let challenge = Challenge::from_route(&route("CRANE")).unwrap();
+        assert_eq!(challenge.solution(), "CRANE");
+        assert_eq!(Challenge::from_route(&Route::Practice), None);
+    }
+
+    #[test]
+    fn ids_are_stable_and_never_zero() {
+        let wordlist = wordlist();
+        let crane = Challenge::new(&Language::English, "crane", &wordlist);
+        let other = Challenge::new(&Language::German, "crane", &wordlist);
+        let (crane, other) = (crane.unwrap(), other.unwrap());
+        assert_eq!(crane.id(), crane.clone().id());
+        assert_ne!(crane.id(), other.id());
+        assert_ne!(crane.id(), 0);
+    }
+}
@@ -1,9 +1,14 @@
 use yew::{
-    function_component, hook, html, use_context, use_effect_with, use_state,
-    Children, ContextProvider, Html, Properties,
+    function_component, hook, html, use_context, Children, ContextProvider,
+    Html, Properties,
 };
 
-use crate::{api, api::Wordle, task::spawn};
+use crate::{
+    api,
+    api::Wordle,
+    components::view_resource,
+    state::{use_language, use_resource, use_route, Language},
+};
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct ConfigProviderProps {
@@ -13,31 +18,46 @@ pub struct ConfigProviderProps {
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Config {
     pub wordle: Wordle,
+    pub language: Language,
+    /// Number of letters of the solution
+    pub length: usize,
 }
 
 #[function_component]
 pub fn ConfigProvider(props: &ConfigProviderProps) -> Html {
     let ConfigProviderProps { children } = props;
-    let config = use_state(Config::default);
-
-    {
-        let config = config.clone();
-        use_effect_with((), move |_| {
-            spawn(async move {
-                let wordle = api::load_wordle().await?;
-                config.set(Config { wordle });
-                Ok(())
-            });
-
-            || {}
-        });
-    }
+    let language = use_language();
+    // The puzzle of the archive, or else today's
+    let date = use_route().date();
+    let key = (language, date);
+    let config = use_resource(key, |(language, date)| async move {
+        let client = api::ApiClient::default();
+        let (wordle, length) = match api::server_mode() {
+            true => {
+                let puzzle = client.load_puzzle(&language, date).await?;
+                let length = puzzle.length;
+                (Wordle::from(puzzle), length)
+            }
+            false => {
+                let wordle = client.load_wordle(&language, date).await?;
+                let length = wordle.solution.chars().count();
+                (wordle, length)
+            }
+        };
+        Ok(Config {
+            wordle,
+            language,
+            length,
+        })
+    });
 
-    html! {
-        <ContextProvider<Config> context={(*config).clone()}>
-            {children}
-        </ContextProvider<Config>>
-    }
+    view_resource(&config, |config| {
+        html! {
+            <ContextProvider<Config> context={config.clone()}>
+                {children.clone()}
+            </ContextProvider<Config>>
+        }
+    })
 }
 
 #[hook]
@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::{fmt, rc::Rc};
 
 use anyhow::Result;
 use gloo::storage::{LocalStorage, Storage};
@@ -9,7 +9,15 @@ use yew::{
     Reducible,
 };
 
-use crate::{api::Wordle, state::use_config};
+use crate::{
+    api::{self, Wordle},
+    i18n::I18n,
+    state::{
+        merge, normalize, use_config, use_route, use_wordlist, Challenge,
+        Config, Route, Wordlist, CHALLENGE_NAMESPACE,
+    },
+    task::spawn,
+};
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub enum Hint {
@@ -27,44 +35,92 @@ impl Hint {
         }
         .into()
     }
+
+    /// Message key of the spoken hint
+    pub fn to_message_key(&self) -> &'static str {
+        match self {
+            Hint::Correct => "hint-correct",
+            Hint::Incorrect => "hint-incorrect",
+            Hint::Misplaced => "hint-misplaced",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 pub struct Guess(String);
 
-impl Guess {
-    pub fn to_string(&self) -> String {
-        self.0.clone()
+impl fmt::Display for Guess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
     }
+}
 
+impl Guess {
     pub fn matches(&self, solution: &str) -> bool {
-        self.0.to_uppercase() == solution.to_uppercase()
+        normalize(&self.0) == normalize(solution)
     }
 
     pub fn new(value: String) -> Guess {
         Guess(value)
     }
 
+    /// Number of letters in the guess
+    pub fn len(&self) -> usize {
+        normalize(&self.0).chars().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     pub fn hints(&self, solution: &str) -> Vec<Hint> {
         let Self(value) = self;
-        let value = value.to_uppercase();
-        let solution = solution.to_uppercase();
+        let value: Vec<char> = normalize(value).chars().collect();
+        let solution: Vec<char> = normalize(solution).chars().collect();
+
+        // Letters of the solution which are not matched exactly
+        // can be used up once as a misplaced hint.
+        let mut unmatched: Vec<char> = value
+            .iter()
+            .zip(solution.iter())
+            .filter(|(a, b)| a != b)
+            .map(|(_, b)| *b)
+            .collect();
+
         value
-            .chars()
-            .zip(solution.chars())
+            .iter()
+            .zip(solution.iter())
             .map(|(a, b)| {
                 if a == b {
-                    Hint::Correct
-                } else if solution.contains(a) {
-                    Hint::Misplaced
-                } else {
-                    Hint::Incorrect
+                    return Hint::Correct;
+                }
+                match unmatched.iter().position(|c| c == a) {
+                    Some(pos) => {
+                        unmatched.swap_remove(pos);
+                        Hint::Misplaced
+                    }
+                    None => Hint::Incorrect,
                 }
             })
             .collect()
     }
 }
 
+impl Guess {
+    /// Describe the hints of the guess for screen readers,
+    /// e.g. "B, correct; R, absent; ..."
+    pub fn describe(&self, hints: &[Hint], i18n: &I18n) -> String {
+        normalize(&self.0)
+            .chars()
+            .zip(hints)
+            .map(|(c, hint)| {
+                format!("{}, {}", c, i18n.t(hint.to_message_key()))
+            })
+            .collect::<Vec<String>>()
+            .join("; ")
+    }
+}
+
 impl<T> From<T> for Guess
 where
     T: Into<String>,
@@ -74,14 +130,84 @@ where
     }
 }
 
+/// Something the player did in a game
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum GameEventKind {
+    /// The current guess after a keystroke
+    Typed(Guess),
+    Submitted(Guess),
+    /// A guess was not accepted, with the message key of the reason
+    Rejected(Guess, String),
+    /// The last guess was taken back
+    Undone(Guess),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GameEvent {
+    /// Milliseconds since the epoch
+    pub at: i64,
+    pub kind: GameEventKind,
+}
+
+/// Milliseconds since the epoch
+pub fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Format a duration in milliseconds as `m:ss` or `h:mm:ss`
+pub fn format_time(millis: i64) -> String {
+    let seconds = millis.max(0) / 1000;
+    let (hours, minutes, seconds) =
+        (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    match hours {
+        0 => format!("{}:{:02}", minutes, seconds),
+        _ => format!("{}:{:02}:{:02}", hours, minutes, seconds),
+    }
+}
+
+impl GameEvent {
+    pub fn now(kind: GameEventKind) -> Self {
+        GameEvent {
+            at: now_millis(),
+            kind,
+        }
+    }
+}
+
+/// Namespace of practice games
+pub const PRACTICE_NAMESPACE: &str = "practice";
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum GameAction {
-    SetSolution(u32, String),
+    SetSolution {
+        namespace: String,
+        id: u32,
+        solution: String,
+        /// Number of letters, needed when the solution is
+        /// kept on the server
+        length: usize,
+    },
     SetCurrent(String),
     AddGuess,
-    /*
-    Clear,
-    */
+    /// Add a guess scored by the server. The guess is
+    /// only added if it is still the current one.
+    AddScoredGuess(Guess, Vec<Hint>),
+    /// The solution, revealed by the server after the game
+    RevealSolution(String),
+    /// The current guess was rejected, with the message key
+    /// of the reason
+    Reject(String),
+    /// Clear the current row
+    ClearCurrent,
+    /// Take back the last guess, only in practice games
+    UndoGuess,
+    /// Start the game over, for debugging
+    Reset,
+    /// Start a practice game, which is never stored
+    Practice(String),
+    /// A copy of the game from another device, merged
+    /// into the current game if it is the same one
+    Synced(Game),
 }
 
 pub fn get_keys(prefix: &str) -> Vec<String> {
@@ -99,7 +225,8 @@ pub fn get_keys(prefix: &str) -> Vec<String> {
     keys
 }
 
-pub fn get_games() -> Result<Vec<Game>> {
+/// Get all stored games of a namespace
+pub fn get_games(namespace: &str) -> Result<Vec<Game>> {
     let keys = get_keys("game:");
     let mut games: Vec<Game> = keys
         .iter()
@@ -107,6 +234,7 @@ pub fn get_games() -> Result<Vec<Game>> {
             let game: Game = LocalStorage::get(key.clone()).unwrap();
             game
         })
+        .filter(|game| game.namespace == namespace)
         .collect::<Vec<Game>>();
     games.sort_by_key(|game| game.id);
     Ok(games)
@@ -133,11 +261,31 @@ impl State {
 
 #[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
 pub struct Game {
+    #[serde(default)]
+    pub namespace: String,
     pub id: u32,
+    /// The solution, empty while it is only known to the server
     pub solution: String,
+    #[serde(default)]
+    pub length: usize,
     pub guesses: Vec<Guess>,
+    /// Hints of the guesses as scored by the server
+    #[serde(default)]
+    pub hints: Vec<Vec<Hint>>,
     pub current: Guess,
     pub state: State,
+    /// Practice games allow taking back guesses
+    #[serde(default)]
+    pub practice: bool,
+    /// Everything the player did, in order
+    #[serde(default)]
+    pub events: Vec<GameEvent>,
+    /// Milliseconds since the epoch of the first keystroke
+    #[serde(default)]
+    pub started_at: Option<i64>,
+    /// Milliseconds since the epoch when the game was won or lost
+    #[serde(default)]
+    pub finished_at: Option<i64>,
 }
 
 impl Game {
@@ -149,9 +297,17 @@ impl Game {
         .update()
     }
 
+    /// Games of the default namespace are stored as `game:<id>`,
+    /// all others as `game:<namespace>:<id>`.
+    pub fn storage_key(&self) -> String {
+        match self.namespace.as_str() {
+            "" => format!("game:{}", self.id),
+            namespace => format!("game:{}:{}", namespace, self.id),
+        }
+    }
+
     pub fn restore(self) -> Self {
-        let key = format!("game:{}", self.id);
-        let game = LocalStorage::get(key);
+        let game = LocalStorage::get(self.storage_key());
         match game {
             Ok(game) => game,
             Err(_) => self,
@@ -162,50 +318,125 @@ impl Game {
         if self.id == 0 {
             return;
         }
-        let key = format!("game:{}", self.id);
-        LocalStorage::set(key, self).expect("failed to save game");
+        LocalStorage::set(self.storage_key(), self)
+            .expect("failed to save game");
+    }
+
+    /// Record an event. The first one starts the clock.
+    fn record(&mut self, kind: GameEventKind) {
+        let event = GameEvent::now(kind);
+        self.started_at.get_or_insert(event.at);
+        self.events.push(event);
+    }
+
+    /// Time from the first keystroke to the end of the game
+    /// in milliseconds
+    pub fn solve_time(&self) -> Option<i64> {
+        Some(self.finished_at? - self.started_at?)
     }
 
     pub fn tries(&self) -> usize {
         self.guesses.len()
     }
 
+    /// Number of letters of the solution
+    pub fn word_length(&self) -> usize {
+        match self.solution.is_empty() {
+            true => self.length,
+            false => self.solution.chars().count(),
+        }
+    }
+
+    /// Hints of all guesses: scored locally when the solution
+    /// is known, otherwise as received from the server.
+    pub fn guess_hints(&self) -> Vec<Vec<Hint>> {
+        match self.solution.is_empty() {
+            true => self.hints.clone(),
+            false => self
+                .guesses
+                .iter()
+                .map(|guess| guess.hints(&self.solution))
+                .collect(),
+        }
+    }
+
+    /// Check if a guess uses all hints revealed so far:
+    /// correct letters must stay in place and misplaced
+    /// letters must be reused.
+    pub fn satisfies_hard_mode(&self, guess: &Guess) -> bool {
+        let candidate: Vec<char> =
+            normalize(&guess.to_string()).chars().collect();
+        self.guesses
+            .iter()
+            .zip(self.guess_hints())
+            .all(|(previous, hints)| {
+                let letters = normalize(&previous.to_string());
+                letters
+                    .chars()
+                    .zip(hints)
+                    .enumerate()
+                    .all(|(i, (c, hint))| match hint {
+                        Hint::Correct => candidate.get(i) == Some(&c),
+                        Hint::Misplaced => candidate.contains(&c),
+                        Hint::Incorrect => true,
+                    })
+            })
+    }
+
     pub fn update(self) -> Self {
         let max_tries = 6;
         let mut game = self.clone();
 
-        // Get last guess from history
-        let last_guess = game.guesses.last();
+        // Get hints of the last guess from history
+        let hints = game.guess_hints();
+        let last_hints = hints.last();
 
         let next_state = {
-            match last_guess {
+            match last_hints {
                 None => State::Running,
-                Some(last_guess) => {
-                    match last_guess.matches(&game.solution) {
+                Some(last_hints) => {
+                    let solved = !last_hints.is_empty()
+                        && last_hints.iter().all(|h| *h == Hint::Correct);
+                    match solved {
                         true => State::Win,
-                        false => {
-                            match game.tries() >= max_tries {
-                                true => State::Loss,
-                                false => State::Running,
-                            }
-                        }
+                        false => match game.tries() >= max_tries {
+                            true => State::Loss,
+                            false => State::Running,
+                        },
                     }
                 }
             }
         };
         game.state = next_state;
+        // Games without a start predate time tracking
+        game.finished_at = match (&game.state, game.started_at) {
+            (State::Running, _) | (_, None) => None,
+            _ => game.finished_at.or_else(|| Some(now_millis())),
+        };
         game.save();
         game
     }
 
     pub fn to_share(&self) -> String {
-        let text = format!("Nerdle {}/6\n\n", self.tries());
+        // Challenges are told apart by their id
+        let title = match self.namespace.as_str() {
+            CHALLENGE_NAMESPACE => format!("Nerdle ⚔ {:08x}", self.id),
+            _ => "Nerdle".into(),
+        };
+        let text = match self.solve_time() {
+            Some(time) => format!(
+                "{} {}/6 ⏱ {}\n\n",
+                title,
+                self.tries(),
+                format_time(time)
+            ),
+            None => format!("{} {}/6\n\n", title, self.tries()),
+        };
         let text = text
             + &self
-                .guesses
+                .guess_hints()
                 .iter()
-                .map(|guess| {
-                    let hints = guess.hints(&self.solution);
+                .map(|hints| {
                     let hints = hints
                         .iter()
                         .map(|hint| match hint {
@@ -226,28 +457,105 @@ impl Reducible for Game {
 
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
         let next_state = match action {
-            GameAction::SetSolution(id, solution) => {
+            GameAction::SetSolution {
+                namespace,
+                id,
+                solution,
+                length,
+            } => {
+                if self.namespace == namespace && self.id == id {
+                    return self;
+                }
                 let game = Game {
+                    namespace,
                     id,
                     solution,
-                    ..(*self).clone()
+                    length,
+                    ..Default::default()
                 };
                 game.restore()
             }
-            GameAction::SetCurrent(current) => Game {
-                current: current.into(),
+            GameAction::SetCurrent(current) => {
+                let mut game = (*self).clone();
+                game.current = current.into();
+                game.record(GameEventKind::Typed(game.current.clone()));
+                game
+            }
+            GameAction::AddGuess => {
+                // A shorter word could match the start of the solution
+                if self.current.len() != self.word_length() {
+                    return self;
+                }
+                let mut game = (*self).clone();
+                let guess = std::mem::take(&mut game.current);
+                game.record(GameEventKind::Submitted(guess.clone()));
+                game.guesses.push(guess);
+                game
+            }
+            GameAction::AddScoredGuess(guess, hints) => {
+                if guess != self.current {
+                    return self;
+                }
+                let mut game = (*self).clone();
+                game.record(GameEventKind::Submitted(guess.clone()));
+                game.guesses.push(guess);
+                game.hints.push(hints);
+                game.current = Guess::default();
+                game
+            }
+            GameAction::Reject(reason) => {
+                let mut game = (*self).clone();
+                let guess = game.current.clone();
+                game.record(GameEventKind::Rejected(guess, reason));
+                game
+            }
+            GameAction::RevealSolution(solution) => Game {
+                solution,
                 ..(*self).clone()
             },
-            GameAction::AddGuess => {
-                let mut guesses = (*self).guesses.clone();
-                let guess = self.current.clone();
-                guesses.push(guess);
-                Game {
-                    guesses,
-                    current: Guess::default(),
-                    ..(*self).clone()
+            GameAction::ClearCurrent => {
+                let mut game = (*self).clone();
+                game.current = Guess::default();
+                game.record(GameEventKind::Typed(Guess::default()));
+                game
+            }
+            GameAction::UndoGuess => {
+                if !self.practice || self.guesses.is_empty() {
+                    return self;
                 }
+                let mut game = (*self).clone();
+                let guess = game.guesses.pop().unwrap_or_default();
+                if game.hints.len() > game.guesses.len() {
+                    game.hints.pop();
+                }
+                game.record(GameEventKind::Undone(guess));
+                game
             }
+            GameAction::Reset => Game {
+                namespace: self.namespace.clone(),
+                id: self.id,
+                solution: self.solution.clone(),
+                length: self.length,
+                practice: self.practice,
+                ..Default::default()
+            },
+            GameAction::Synced(game) => {
+                if game.storage_key() != self.storage_key() {
+                    return self;
+                }
+                let merged = merge(&self, &game);
+                if merged == *self {
+                    return self;
+                }
+                merged
+            }
+            GameAction::Practice(solution) => Game {
+                namespace: PRACTICE_NAMESPACE.into(),
+                length: solution.chars().count(),
+                solution,
+                practice: true,
+                ..Default::default()
+            },
         };
         let next_state = next_state.update();
         next_state.into()
@@ -259,9 +567,19 @@ pub struct History {
     games: Vec<Game>,
 }
 
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl History {
     pub fn new() -> Self {
-        let games = get_games().unwrap();
+        Self::for_namespace("")
+    }
+
+    pub fn for_namespace(namespace: &str) -> Self {
+        let games = get_games(namespace).unwrap();
         Self { games }
     }
 
@@ -283,9 +601,20 @@ impl History {
     /// Get current streak
     pub fn streak(&self) -> usize {
         let reverse = self.games.iter().rev();
-        let streak =
-            reverse.take_while(|game| game.state == State::Win).count();
-        streak
+        reverse.take_while(|game| game.state == State::Win).count()
+    }
+
+    /// Won games with the fastest solve times, up to `count`
+    pub fn fastest(&self, count: usize) -> Vec<(&Game, i64)> {
+        let mut times: Vec<(&Game, i64)> = self
+            .games
+            .iter()
+            .filter(|game| game.state == State::Win)
+            .filter_map(|game| Some((game, game.solve_time()?)))
+            .collect();
+        times.sort_by_key(|(_, time)| *time);
+        times.truncate(count);
+        times
     }
 
     /// Get max streak
@@ -304,6 +633,52 @@ impl History {
     }
 }
 
+/// The action which plays the puzzle of a route, none while
+/// the config of a challenge's language is loading
+pub fn route_puzzle(
+    route: &Route,
+    config: &Config,
+    wordlist: &Wordlist,
+) -> Option<GameAction> {
+    let action = match (route, Challenge::from_route(route)) {
+        (_, Some(challenge)) if challenge.language != config.language => {
+            return None;
+        }
+        (_, Some(challenge)) => GameAction::SetSolution {
+            namespace: CHALLENGE_NAMESPACE.into(),
+            id: challenge.id(),
+            solution: challenge.solution(),
+            length: challenge.word.chars().count(),
+        },
+        (Route::Practice, _) => {
+            let solution = practice_word(wordlist);
+            GameAction::Practice(config.language.fold(&solution))
+        }
+        // The puzzle of the day or of the archive,
+        // also for broken challenge links
+        _ => {
+            let Wordle { id, solution, .. } = config.wordle.clone();
+            GameAction::SetSolution {
+                namespace: config.language.namespace(),
+                id,
+                solution,
+                length: config.length,
+            }
+        }
+    };
+    Some(action)
+}
+
+/// Pick a random word for practice
+pub fn practice_word(wordlist: &Wordlist) -> String {
+    let words = wordlist.words();
+    let index = (js_sys::Math::random() * words.len() as f64) as usize;
+    words
+        .get(index.min(words.len().saturating_sub(1)))
+        .map(|word| word.to_string())
+        .unwrap_or_default()
+}
+
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct GameStateProviderProps {
     pub children: Children,
@@ -325,22 +700,44 @@ impl GameContext {
 pub fn GameStateProvider(props: &GameStateProviderProps) -> Html {
     let GameStateProviderProps { children } = props;
     let config = use_config();
+    let language = config.language.clone();
     let game = use_reducer(Game::default);
     let dispatch =
         use_callback(game.clone(), |action, game| game.dispatch(action));
 
+    // Play the puzzle of the route
     {
-        let config = config.clone();
         let game = game.clone();
-        use_effect_with((game, config), |(game, config)| {
-            let Wordle { id, solution, .. } = config.wordle.clone();
-            game.dispatch(GameAction::SetSolution(id, solution));
+        let wordlist = use_wordlist();
+        let route = use_route();
+        use_effect_with((config, route), move |(config, route)| {
+            if let Some(action) = route_puzzle(route, config, &wordlist) {
+                game.dispatch(action);
+            }
+        });
+    }
+
+    // Games scored by the server learn the solution once over
+    {
+        let dispatch = game.dispatcher();
+        use_effect_with((*game).clone(), move |game| {
+            if game.state == State::Loss && game.solution.is_empty() {
+                let id = game.id;
+                let guesses = game.guesses.clone();
+                spawn(async move {
+                    let solution = api::ApiClient::default()
+                        .reveal_solution(&language, id, &guesses)
+                        .await?;
+                    dispatch.dispatch(GameAction::RevealSolution(solution));
+                    Ok(())
+                });
+            }
         });
     }
 
     let ctx = GameContext {
         dispatch,
-        game: (*game).clone().into(),
+        game: (*game).clone(),
     };
 
     html! {
@@ -359,3 +756,163 @@ pub fn use_game_context() -> GameContext {
 pub fn use_game_state() -> Game {
     use_game_context().game
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A game which is never persisted, as its id is 0
+    fn game(solution: &str) -> Rc<Game> {
+        Rc::new(Game::new(solution))
+    }
+
+    fn play(game: Rc<Game>, word: &str) -> Rc<Game> {
+        let game = game.reduce(GameAction::SetCurrent(word.into()));
+        game.reduce(GameAction::AddGuess)
+    }
+
+    #[test]
+    fn adds_guesses_until_solved() {
+        let game = play(game("BROTH"), "CRANE");
+        assert_eq!(game.tries(), 1);
+        assert_eq!(game.state, State::Running);
+        assert!(game.current.is_empty());
+
+        let game = play(game, "BROTH");
+        assert_eq!(game.state, State::Win);
+    }
+
+    #[test]
+    fn ignores_guesses_of_the_wrong_length() {
+        let game = play(game("CRANES"), "CRANE");
+        assert_eq!(game.tries(), 0);
+        assert_eq!(game.state, State::Running);
+    }
+
+    #[test]
+    fn uses_up_misplaced_letters() {
+        use Hint::*;
+        assert_eq!(
+            Guess::from("LLAMA").hints("HELLO"),
+            [Misplaced, Misplaced, Incorrect, Incorrect, Incorrect]
+        );
+        // The only A of the solution marks only the first A
+        assert_eq!(
+            Guess::from("LLAMA").hints("ALLOW"),
+            [Misplaced, Correct, Misplaced, Incorrect, Incorrect]
+        );
+        // Exact matches come first, leaving one E to be misplaced
+        assert_eq!(
+            Guess::from("EERIE").hints("THREE"),
+            [Misplaced, Incorrect, Correct, Incorrect, Correct]
+        );
+    }
+
+    #[test]
+    fn scores_letters_beyond_ascii() {
+        use Hint::*;
+        assert_eq!(
+            Guess::from("äpfel").hints("ÄHREN"),
+            [Correct, Incorrect, Incorrect, Correct, Incorrect]
+        );
+        assert!(Guess::from("straße").matches("STRAẞE"));
+        // Decomposed input scores like composed input
+        let decomposed = Guess::from("A\u{308}HREN");
+        assert_eq!(decomposed.len(), 5);
+        assert!(decomposed.matches("\u{c4}hren"));
+        assert_eq!(decomposed.hints("ÄHREN"), vec![Correct; 5]);
+    }
+
+    #[test]
+    fn loses_after_six_tries() {
+        let game = (0..6).fold(game("BROTH"), |game, _| play(game, "CRANE"));
+        assert_eq!(game.state, State::Loss);
+    }
+
+    #[test]
+    fn clears_the_current_row() {
+        let game = game("BROTH").reduce(GameAction::SetCurrent("CRA".into()));
+        let game = game.reduce(GameAction::ClearCurrent);
+        assert!(game.current.is_empty());
+        assert_eq!(
+            game.events.last().map(|event| &event.kind),
+            Some(&GameEventKind::Typed(Guess::default()))
+        );
+    }
+
+    #[test]
+    fn undoes_guesses_only_in_practice() {
+        let daily = play(game("BROTH"), "CRANE");
+        let daily = daily.reduce(GameAction::UndoGuess);
+        assert_eq!(daily.tries(), 1);
+
+        let practice = Rc::new(Game {
+            practice: true,
+            ..Game::new("BROTH")
+        });
+        let practice = play(play(practice, "CRANE"), "BROTH");
+        assert_eq!(practice.state, State::Win);
+        let practice = practice.reduce(GameAction::UndoGuess);
+        assert_eq!(practice.tries(), 1);
+        assert_eq!(practice.state, State::Running);
+        assert_eq!(
+            practice.events.last().map(|event| &event.kind),
+            Some(&GameEventKind::Undone(Guess::from("BROTH")))
+        );
+    }
+
+    #[test]
+    fn undoes_scored_guesses_with_their_hints() {
+        let game = Rc::new(Game {
+            practice: true,
+            length: 5,
+            ..Game::default()
+        });
+        let game = game.reduce(GameAction::SetCurrent("CRANE".into()));
+        let hints = vec![Hint::Incorrect; 5];
+        let game =
+            game.reduce(GameAction::AddScoredGuess("CRANE".into(), hints));
+        assert_eq!(game.guess_hints().len(), 1);
+        let game = game.reduce(GameAction::UndoGuess);
+        assert!(game.guess_hints().is_empty());
+    }
+
+    #[test]
+    fn starts_practice_games() {
+        let game = play(game("BROTH"), "CRANE");
+        let game = game.reduce(GameAction::Practice("PLANTS".into()));
+        assert!(game.practice);
+        assert_eq!(game.namespace, PRACTICE_NAMESPACE);
+        assert_eq!(game.id, 0);
+        assert_eq!(game.word_length(), 6);
+        assert_eq!(game.tries(), 0);
+    }
+
+    #[test]
+    fn tracks_the_solve_time() {
+        let game = game("BROTH");
+        assert_eq!(game.started_at, None);
+        let game = play(game, "CRANE");
+        assert!(game.started_at.is_some());
+        assert_eq!(game.finished_at, None);
+        let game = play(game, "BROTH");
+        assert!(game.solve_time().is_some_and(|time| time >= 0));
+        assert!(game.to_share().starts_with("Nerdle 2/6 ⏱ 0:0"));
+    }
+
+    #[test]
+    fn formats_times() {
+        assert_eq!(format_time(61_500), "1:01");
+        assert_eq!(format_time(3_723_000), "1:02:03");
+    }
+
+    #[test]
+    fn resets_the_game() {
+        let game = play(game("BROTH"), "BROTH");
+        let game = game.reduce(GameAction::Reset);
+        assert_eq!(game.state, State::Running);
+        assert!(game.guesses.is_empty());
+        assert!(game.events.is_empty());
+        assert_eq!(game.solution, "BROTH");
+    }
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::state::KeyboardLayout;
+
+/// Normalize a word for comparison and scoring: compose
+/// the characters (NFC) and uppercase them one by one.
+/// The ß becomes the capital ẞ instead of SS, and other
+/// characters without a single char uppercase form are kept
+/// as they are, so the length of a word never changes.
+pub fn normalize(word: &str) -> String {
+    word.trim()
+        .nfc()
+        .map(|c| {
+            let mut upper = c.to_uppercase();
+            match (c, upper.next(), upper.next()) {
+                ('ß', _, _) => 'ẞ',
+                (_, Some(u), None) => u,
+                _ => c,
+            }
+        })
+        .collect()
+}
+
+/// A language pack bundles the word list and daily answers
+/// with the letters of the keyboard.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Spanish,
+}
+
+impl Language {
+    /// All available language packs
+    pub fn all() -> Vec<Language> {
+        vec![Language::English, Language::German, Language::Spanish]
+    }
+
+    pub fn from_code(code: &str) -> Option<Language> {
+        Language::all()
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    pub fn code(&self) -> String {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::Spanish => "es",
+        }
+        .into()
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::Spanish => "Español",
+        }
+        .into()
+    }
+
+    /// Directory holding the word list and puzzles
+    pub fn data_path(&self) -> String {
+        match self {
+            Language::English => "data".into(),
+            _ => format!("data/{}", self.code()),
+        }
+    }
+
+    /// Namespace for persisting games. English keeps the
+    /// empty namespace of the games stored before language
+    /// packs existed.
+    pub fn namespace(&self) -> String {
+        match self {
+            Language::English => "".into(),
+            _ => self.code(),
+        }
+    }
+
+    /// The layout that fits the language best
+    pub fn keyboard_layout(&self) -> KeyboardLayout {
+        match self {
+            Language::English => KeyboardLayout::Qwerty,
+            Language::German => KeyboardLayout::Qwertz,
+            Language::Spanish => KeyboardLayout::Qwerty,
+        }
+    }
+
+    /// Additional letters, appended to the keyboard rows
+    pub fn extra_keys(&self) -> Vec<Vec<&'static str>> {
+        match self {
+            Language::English => vec![],
+            Language::German => vec![vec!["Ü"], vec!["Ö", "Ä"]],
+            Language::Spanish => vec![vec![], vec!["Ñ"]],
+        }
+    }
+
+    /// Fold a word into the alphabet of the language:
+    /// Spanish ignores accents on vowels but keeps the Ñ.
+    pub fn fold(&self, word: &str) -> String {
+        let word = normalize(word);
+        match self {
+            Language::Spanish => word
+                .nfd()
+                .filter(|c| !matches!(c, '\u{0300}' | '\u{0301}'))
+                .nfc()
+                .collect(),
+            _ => word,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_without_changing_the_length() {
+        assert_eq!(normalize(" crane "), "CRANE");
+        assert_eq!(normalize("ähren"), "ÄHREN");
+        assert_eq!(normalize("straße"), "STRAẞE");
+        assert_eq!(normalize("STRAẞE"), "STRAẞE");
+        assert_eq!(normalize("a\u{308}hren"), "\u{c4}HREN");
+    }
+
+    #[test]
+    fn folds_accents_only_in_spanish() {
+        let spanish = Language::Spanish;
+        assert_eq!(spanish.fold("árbol"), "ARBOL");
+        assert_eq!(spanish.fold("caÑón"), "CAÑON");
+        assert_eq!(spanish.fold("can\u{303}o\u{301}n"), "CAÑON");
+        assert_eq!(Language::German.fold("über"), "ÜBER");
+        assert_eq!(Language::English.fold("crane"), "CRANE");
+    }
+}
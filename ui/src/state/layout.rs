@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+/// Special keys, placed in the rows like any other glyph.
+pub const KEY_ENTER: &str = "ENTER";
+pub const KEY_BKSP: &str = "BKSP";
+/// Clears the current row, only on the physical keyboard
+pub const KEY_CLEAR: &str = "CLEAR";
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Qwertz,
+    Azerty,
+    Dvorak,
+    Colemak,
+}
+
+impl KeyboardLayout {
+    /// All available layout presets
+    pub fn all() -> Vec<KeyboardLayout> {
+        vec![
+            KeyboardLayout::Qwerty,
+            KeyboardLayout::Qwertz,
+            KeyboardLayout::Azerty,
+            KeyboardLayout::Dvorak,
+            KeyboardLayout::Colemak,
+        ]
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            KeyboardLayout::Qwerty => "QWERTY",
+            KeyboardLayout::Qwertz => "QWERTZ",
+            KeyboardLayout::Azerty => "AZERTY",
+            KeyboardLayout::Dvorak => "Dvorak",
+            KeyboardLayout::Colemak => "Colemak",
+        }
+        .into()
+    }
+
+    /// Get the rows of keys, including the placement
+    /// of ENTER and BKSP.
+    pub fn rows(&self) -> Vec<Vec<&'static str>> {
+        let rows: [&str; 3] = match self {
+            KeyboardLayout::Qwerty => [
+                "Q W E R T Y U I O P",
+                "A S D F G H J K L",
+                "ENTER Z X C V B N M BKSP",
+            ],
+            KeyboardLayout::Qwertz => [
+                "Q W E R T Z U I O P",
+                "A S D F G H J K L",
+                "ENTER Y X C V B N M BKSP",
+            ],
+            KeyboardLayout::Azerty => [
+                "A Z E R T Y U I O P",
+                "Q S D F G H J K L M",
+                "W X C V B N BKSP ENTER",
+            ],
+            KeyboardLayout::Dvorak => [
+                "P Y F G C R L BKSP",
+                "A O E U I D H T N S",
+                "ENTER Q J K X B M W V Z",
+            ],
+            KeyboardLayout::Colemak => [
+                "Q W F P G J L U Y",
+                "A R S T D H N E I O",
+                "ENTER Z X C V B K M BKSP",
+            ],
+        };
+        rows.iter()
+            .map(|row| row.split_whitespace().collect())
+            .collect()
+    }
+
+    /// Get the rows with additional letters of a language
+    /// appended to the letter rows.
+    pub fn rows_with(
+        &self,
+        extra_keys: Vec<Vec<&'static str>>,
+    ) -> Vec<Vec<&'static str>> {
+        let mut rows = self.rows();
+        for (row, extra) in rows.iter_mut().zip(extra_keys) {
+            let pos = row
+                .iter()
+                .rposition(|key| *key != KEY_ENTER && *key != KEY_BKSP)
+                .map_or(row.len(), |pos| pos + 1);
+            row.splice(pos..pos, extra);
+        }
+        rows
+    }
+}
@@ -0,0 +1,54 @@
+use yew::{hook, use_effect_with};
+
+use crate::{
+    api::{self, ResultSubmission},
+    state::{use_config, use_game_state, use_settings, State},
+    task::spawn,
+};
+
+/// Leaderboards are kept by the API server
+pub fn leaderboard_available() -> bool {
+    api::server_mode()
+}
+
+/// Submit the result of a finished daily puzzle to the
+/// leaderboard of the group, if the player joined one.
+/// The server keeps the first result of a puzzle, so
+/// submitting again after a reload does no harm.
+#[hook]
+pub fn use_result_submission() {
+    let game = use_game_state();
+    let settings = use_settings();
+    let language = use_config().language;
+
+    let daily = game.namespace == language.namespace()
+        && game.id != 0
+        && !game.practice;
+    let submit = (leaderboard_available()
+        && daily
+        && game.state != State::Running
+        && settings.leaderboard_enabled())
+    .then(|| ResultSubmission {
+        nickname: settings.nickname.clone(),
+        id: game.id,
+        tries: game.tries(),
+        solved: game.state == State::Win,
+        time: game.solve_time(),
+        hard_mode: settings.hard_mode,
+    });
+
+    use_effect_with(
+        (submit, settings.group.clone()),
+        move |(submit, group)| {
+            if let Some(result) = submit.clone() {
+                let group = group.clone();
+                spawn(async move {
+                    api::ApiClient::default()
+                        .submit_result(&language, &group, &result)
+                        .await?;
+                    Ok(())
+                });
+            }
+        },
+    );
+}
@@ -6,3 +6,33 @@ pub use config::*;
 
 mod wordlist;
 pub use wordlist::*;
+
+mod settings;
+pub use settings::*;
+
+mod layout;
+pub use layout::*;
+
+mod language;
+pub use language::*;
+
+mod modal;
+pub use modal::*;
+
+mod resource;
+pub use resource::*;
+
+mod race;
+pub use race::*;
+
+mod challenge;
+pub use challenge::*;
+
+mod route;
+pub use route::*;
+
+mod leaderboard;
+pub use leaderboard::*;
+
+mod sync;
+pub use sync::*;
@@ -0,0 +1,125 @@
+use gloo::{
+    storage::{LocalStorage, Storage},
+    timers::callback::Timeout,
+};
+use yew::{
+    function_component, hook, html, use_callback, use_context, use_mut_ref,
+    use_state, Callback, Children, ContextProvider, Html, Properties,
+};
+
+const ONBOARDED_KEY: &str = "onboarded";
+
+/// Check if this is the first visit and remember the visit.
+pub fn take_first_visit() -> bool {
+    let onboarded: bool = LocalStorage::get(ONBOARDED_KEY).unwrap_or(false);
+    if !onboarded {
+        let _ = LocalStorage::set(ONBOARDED_KEY, true);
+    }
+    !onboarded
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModalKind {
+    Stats,
+    Help,
+    Settings,
+    Replay,
+    Race,
+    Challenge,
+    Leaderboard,
+}
+
+impl ModalKind {
+    pub fn to_css_class(&self) -> String {
+        match self {
+            ModalKind::Stats => "stats",
+            ModalKind::Help => "help",
+            ModalKind::Settings => "settings",
+            ModalKind::Replay => "replay",
+            ModalKind::Race => "race",
+            ModalKind::Challenge => "challenge",
+            ModalKind::Leaderboard => "leaderboard",
+        }
+        .into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModalAction {
+    Open(ModalKind),
+    /// Open a modal after a delay in milliseconds
+    OpenAfter(ModalKind, u32),
+    Toggle(ModalKind),
+    Close,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ModalContext {
+    pub open: Option<ModalKind>,
+    pub dispatch: Callback<ModalAction>,
+}
+
+impl ModalContext {
+    pub fn dispatch(&self, action: ModalAction) {
+        self.dispatch.emit(action);
+    }
+
+    pub fn is_open(&self, kind: ModalKind) -> bool {
+        self.open == Some(kind)
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ModalProviderProps {
+    pub children: Children,
+}
+
+#[function_component]
+pub fn ModalProvider(props: &ModalProviderProps) -> Html {
+    let ModalProviderProps { children } = props;
+    // First time visitors are greeted with the instructions
+    let open = use_state(|| take_first_visit().then_some(ModalKind::Help));
+
+    // Only a single pending timer exists at any time. Every
+    // action cancels it, so a delayed modal never pops up
+    // after the user already moved on.
+    let timer = use_mut_ref(|| None::<Timeout>);
+
+    let dispatch =
+        use_callback(open.clone(), move |action: ModalAction, open| {
+            if let Some(pending) = timer.borrow_mut().take() {
+                pending.cancel();
+            }
+            match action {
+                ModalAction::Open(kind) => open.set(Some(kind)),
+                ModalAction::OpenAfter(kind, delay) => {
+                    let open = open.clone();
+                    let pending = Timeout::new(delay, move || {
+                        open.set(Some(kind));
+                    });
+                    *timer.borrow_mut() = Some(pending);
+                }
+                ModalAction::Toggle(kind) => match **open == Some(kind) {
+                    true => open.set(None),
+                    false => open.set(Some(kind)),
+                },
+                ModalAction::Close => open.set(None),
+            }
+        });
+
+    let ctx = ModalContext {
+        open: *open,
+        dispatch,
+    };
+
+    html! {
+        <ContextProvider<ModalContext> context={ctx}>
+            {children}
+        </ContextProvider<ModalContext>>
+    }
+}
+
+#[hook]
+pub fn use_modal() -> ModalContext {
+    use_context::<ModalContext>().expect("ModalProvider not found")
+}
@@ -0,0 +1,483 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use futures::{channel::mpsc, SinkExt, StreamExt};
+use gloo::net::websocket::{futures::WebSocket, Message};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::spawn_local;
+use yew::{
+    function_component, hook, html, use_callback, use_context, use_effect_with,
+    use_memo, use_state, Callback, Children, ContextProvider, Html, Properties,
+};
+
+use crate::{
+    api,
+    debug::{log_info, log_warn},
+    state::{
+        game::State, route_puzzle, use_config, use_game_context, use_route,
+        use_wordlist, GameAction, Hint, Language,
+    },
+};
+
+/// A player of a race, as seen by everyone else:
+/// the hints of the guesses, but never the letters.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Player {
+    pub id: u32,
+    pub name: String,
+    pub hints: Vec<Vec<Hint>>,
+    pub state: State,
+}
+
+/// Messages of a player to the relay
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum ClientMessage {
+    /// The board of the player changed
+    Board { hints: Vec<Vec<Hint>>, state: State },
+}
+
+/// Messages of the relay to the players of a room
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum ServerMessage {
+    /// Sent to a player after joining
+    Welcome {
+        player: u32,
+        solution: String,
+        language: Language,
+        players: Vec<Player>,
+    },
+    /// A player joined or changed the board
+    Board {
+        player: Player,
+    },
+    Left {
+        player: u32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RoomInfo {
+    pub code: String,
+}
+
+/// A joined room
+#[derive(Debug, Clone, PartialEq)]
+pub struct Room {
+    pub code: String,
+    /// Id of this player
+    pub me: u32,
+    pub players: Vec<Player>,
+}
+
+impl Room {
+    pub fn opponents(&self) -> impl Iterator<Item = &Player> {
+        self.players.iter().filter(|player| player.id != self.me)
+    }
+
+    fn apply(&mut self, message: ServerMessage) {
+        match message {
+            ServerMessage::Welcome {
+                player, players, ..
+            } => {
+                self.me = player;
+                self.players = players;
+            }
+            ServerMessage::Board { player } => {
+                match self.players.iter_mut().find(|p| p.id == player.id) {
+                    Some(existing) => *existing = player,
+                    None => self.players.push(player),
+                }
+            }
+            ServerMessage::Left { player } => {
+                self.players.retain(|p| p.id != player);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RaceStatus {
+    Idle,
+    Connecting,
+    Joined(Room),
+    /// The connection failed or was lost
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RaceAction {
+    /// Create a room and join it
+    Create {
+        name: String,
+    },
+    Join {
+        code: String,
+        name: String,
+    },
+    Leave,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaceContext {
+    pub status: RaceStatus,
+    pub dispatch: Callback<RaceAction>,
+}
+
+impl RaceContext {
+    pub fn dispatch(&self, action: RaceAction) {
+        self.dispatch.emit(action);
+    }
+
+    pub fn room(&self) -> Option<&Room> {
+        match &self.status {
+            RaceStatus::Joined(room) => Some(room),
+            _ => None,
+        }
+    }
+}
+
+/// Races need a relay, either the API server or a
+/// separate one
+pub fn race_available() -> bool {
+    api::ApiClient::relay().is_some()
+}
+
+/// Namespace of race games. Races use id 0, so they
+/// are never stored.
+fn race_namespace(code: &str) -> String {
+    format!("race:{}", code)
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct RaceProviderProps {
+    pub children: Children,
+}
+
+/// Connection to a race room. While in a room, the game is
+/// played on the solution of the room and every change of
+/// the board is sent to the other players.
+#[function_component]
+pub fn RaceProvider(props: &RaceProviderProps) -> Html {
+    let RaceProviderProps { children } = props;
+    let status = use_state(|| RaceStatus::Idle);
+    let config = use_config();
+    let route = use_route();
+    let wordlist = use_wordlist();
+    let game = use_game_context();
+    let connections = use_memo((), |_| Connections::default());
+
+    let dispatch = {
+        let status = status.clone();
+        let connections = connections.clone();
+        let game_dispatch = game.dispatch.clone();
+        let deps = (config.clone(), route, wordlist);
+        use_callback(deps, move |action: RaceAction, deps| {
+            let (config, route, wordlist) = deps;
+            let generation = connections.close();
+            let connection = || Connection {
+                connections: connections.clone(),
+                generation,
+                status: {
+                    let status = status.clone();
+                    Callback::from(move |value| status.set(value))
+                },
+                game: game_dispatch.clone(),
+            };
+            match action {
+                RaceAction::Create { name } => {
+                    let language = config.language.clone();
+                    let connection = connection();
+                    status.set(RaceStatus::Connecting);
+                    spawn_local(async move {
+                        let Some(relay) = api::ApiClient::relay() else {
+                            return connection.fail("no relay".into());
+                        };
+                        match relay.create_room(&language).await {
+                            Ok(room) => connection.run(room.code, name).await,
+                            Err(err) => connection.fail(err.to_string()),
+                        }
+                    });
+                }
+                RaceAction::Join { code, name } => {
+                    let connection = connection();
+                    status.set(RaceStatus::Connecting);
+                    spawn_local(connection.run(code.to_uppercase(), name));
+                }
+                RaceAction::Leave => {
+                    status.set(RaceStatus::Idle);
+                    // Back to the puzzle of the route
+                    if let Some(action) = route_puzzle(route, config, wordlist)
+                    {
+                        game_dispatch.emit(action);
+                    }
+                }
+            }
+        })
+    };
+
+    // Share the board after every guess
+    {
+        let connections = connections.clone();
+        let board = (game.game.guess_hints(), game.game.state.clone());
+        let joined = matches!(*status, RaceStatus::Joined(_));
+        use_effect_with((board, joined), move |((hints, state), joined)| {
+            if *joined {
+                connections.send(ClientMessage::Board {
+                    hints: hints.clone(),
+                    state: state.clone(),
+                });
+            }
+        });
+    }
+
+    let ctx = RaceContext {
+        status: (*status).clone(),
+        dispatch,
+    };
+
+    html! {
+        <ContextProvider<RaceContext> context={ctx}>
+            {children.clone()}
+        </ContextProvider<RaceContext>>
+    }
+}
+
+/// The connections of a provider. Every action closes the
+/// current one and starts a new generation, so the messages
+/// of a closed connection can never reach the status.
+#[derive(Default)]
+struct Connections {
+    generation: Cell<u32>,
+    /// Outgoing messages of the current connection
+    outbox: RefCell<Option<mpsc::UnboundedSender<ClientMessage>>>,
+}
+
+impl Connections {
+    /// Close the current connection, returning the generation
+    /// of the next one
+    fn close(&self) -> u32 {
+        if let Some(outbox) = self.outbox.borrow_mut().take() {
+            outbox.close_channel();
+        }
+        self.generation.set(self.generation.get() + 1);
+        self.generation.get()
+    }
+
+    fn send(&self, message: ClientMessage) {
+        if let Some(outbox) = self.outbox.borrow().as_ref() {
+            let _ = outbox.unbounded_send(message);
+        }
+    }
+}
+
+/// A connection to a room, from joining until it is closed
+struct Connection {
+    connections: Rc<Connections>,
+    generation: u32,
+    status: Callback<RaceStatus>,
+    game: Callback<GameAction>,
+}
+
+impl Connection {
+    fn is_current(&self) -> bool {
+        self.connections.generation.get() == self.generation
+    }
+
+    fn set_status(&self, status: RaceStatus) {
+        if self.is_current() {
+            self.status.emit(status);
+        }
+    }
+
+    fn fail(&self, message: String) {
+        log_warn!("Race failed: {}", message);
+        self.set_status(RaceStatus::Failed(message));
+    }
+
+    /// Send messages through the outbox, unless a newer
+    /// connection took over
+    fn open(&self, outbox: mpsc::UnboundedSender<ClientMessage>) {
+        match self.is_current() {
+            true => *self.connections.outbox.borrow_mut() = Some(outbox),
+            false => outbox.close_channel(),
+        }
+    }
+
+    async fn run(self, code: String, name: String) {
+        let Some(relay) = api::ApiClient::relay() else {
+            return self.fail("no relay".into());
+        };
+        // Left while the room was created
+        if !self.is_current() {
+            return;
+        }
+        let socket = match WebSocket::open(&relay.room_url(&code, &name)) {
+            Ok(socket) => socket,
+            Err(err) => return self.fail(err.to_string()),
+        };
+        let (mut write, mut read) = socket.split();
+        let (sender, mut receiver) = mpsc::unbounded::<ClientMessage>();
+        self.open(sender);
+
+        // Forward the outbox until it is closed, which also
+        // closes the socket and ends the read loop below
+        spawn_local(async move {
+            while let Some(message) = receiver.next().await {
+                let Ok(text) = serde_json::to_string(&message) else {
+                    continue;
+                };
+                if write.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+            let _ = write.close().await;
+        });
+
+        let mut room = Room {
+            code: code.clone(),
+            me: 0,
+            players: vec![],
+        };
+        while let Some(message) = read.next().await {
+            // Left, or a newer connection took over
+            if !self.is_current() {
+                return;
+            }
+            let message = match message {
+                Ok(Message::Text(text)) => {
+                    serde_json::from_str::<ServerMessage>(&text)
+                }
+                Ok(Message::Bytes(_)) => continue,
+                Err(err) => return self.fail(err.to_string()),
+            };
+            let message = match message {
+                Ok(message) => message,
+                Err(err) => return self.fail(err.to_string()),
+            };
+            if let ServerMessage::Welcome {
+                solution, language, ..
+            } = &message
+            {
+                log_info!("Joined race {}", code);
+                self.game.emit(GameAction::SetSolution {
+                    namespace: race_namespace(&code),
+                    id: 0,
+                    solution: language.fold(solution),
+                    length: solution.chars().count(),
+                });
+            }
+            room.apply(message);
+            self.set_status(RaceStatus::Joined(room.clone()));
+        }
+        self.fail("connection closed".into());
+    }
+}
+
+#[hook]
+pub fn use_race() -> RaceContext {
+    use_context::<RaceContext>().expect("RaceProvider not found")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(id: u32, rows: usize) -> Player {
+        Player {
+            id,
+            name: format!("player{}", id),
+            hints: vec![vec![Hint::Incorrect; 5]; rows],
+            state: State::Running,
+        }
+    }
+
+    fn connection(
+        connections: &Rc<Connections>,
+        statuses: &Rc<RefCell<Vec<RaceStatus>>>,
+    ) -> Connection {
+        let statuses = statuses.clone();
+        Connection {
+            connections: connections.clone(),
+            generation: connections.close(),
+            status: Callback::from(move |status| {
+                statuses.borrow_mut().push(status)
+            }),
+            game: Callback::noop(),
+        }
+    }
+
+    fn board() -> ClientMessage {
+        ClientMessage::Board {
+            hints: vec![],
+            state: State::Running,
+        }
+    }
+
+    fn ids<'a>(players: impl Iterator<Item = &'a Player>) -> Vec<u32> {
+        players.map(|player| player.id).collect()
+    }
+
+    #[test]
+    fn follows_the_players_of_the_room() {
+        let mut room = Room {
+            code: "ABCDE".into(),
+            me: 0,
+            players: vec![],
+        };
+        room.apply(ServerMessage::Welcome {
+            player: 2,
+            solution: "CRANE".into(),
+            language: Language::English,
+            players: vec![player(1, 1), player(2, 0)],
+        });
+        assert_eq!(room.me, 2);
+        assert_eq!(ids(room.opponents()), [1]);
+
+        // Boards replace the known ones, or announce new players
+        room.apply(ServerMessage::Board {
+            player: player(1, 3),
+        });
+        room.apply(ServerMessage::Board {
+            player: player(3, 0),
+        });
+        assert_eq!(ids(room.players.iter()), [1, 2, 3]);
+        assert_eq!(room.players[0].hints.len(), 3);
+
+        room.apply(ServerMessage::Left { player: 1 });
+        assert_eq!(ids(room.opponents()), [3]);
+    }
+
+    #[test]
+    fn replaced_connections_never_update_the_status() {
+        let connections = Rc::new(Connections::default());
+        let statuses = Rc::new(RefCell::new(vec![]));
+        let first = connection(&connections, &statuses);
+        let (sender, mut first_outbox) = mpsc::unbounded();
+        first.open(sender);
+        first.set_status(RaceStatus::Connecting);
+        connections.send(board());
+        assert_eq!(first_outbox.try_next().unwrap(), Some(board()));
+
+        // Replacing the connection closes its outbox
+        let second = connection(&connections, &statuses);
+        assert_eq!(first_outbox.try_next().unwrap(), None);
+        first.set_status(RaceStatus::Failed("closed".into()));
+        second.set_status(RaceStatus::Failed("lost".into()));
+        assert_eq!(
+            *statuses.borrow(),
+            [RaceStatus::Connecting, RaceStatus::Failed("lost".into())]
+        );
+
+        // An outbox opened too late is closed right away
+        connections.close();
+        let (sender, mut late_outbox) = mpsc::unbounded();
+        second.open(sender);
+        connections.send(board());
+        assert_eq!(late_outbox.try_next().unwrap(), None);
+        assert_eq!(statuses.borrow().len(), 2);
+    }
+}
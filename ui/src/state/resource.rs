@@ -0,0 +1,76 @@
+use std::{future::Future, rc::Rc};
+
+use wasm_bindgen_futures::spawn_local;
+use yew::{
+    hook, use_callback, use_effect_with, use_mut_ref, use_state, Callback,
+};
+
+use crate::debug::log_warn;
+
+/// State of a value loaded in the background
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resource<T> {
+    Loading,
+    Ready(T),
+    /// Loading failed with the given message
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceHandle<T> {
+    pub state: Resource<T>,
+    /// Load the resource again
+    pub retry: Callback<()>,
+}
+
+/// Load a resource whenever `key` changes, or on retry.
+/// A ready value is kept while its successor loads, so a
+/// reload doesn't tear down the app, and results of loads
+/// which were overtaken by a newer one are dropped.
+#[hook]
+pub fn use_resource<K, T, F, Fut>(key: K, load: F) -> ResourceHandle<T>
+where
+    K: PartialEq + Clone + 'static,
+    T: Clone + 'static,
+    F: Fn(K) -> Fut + 'static,
+    Fut: Future<Output = anyhow::Result<T>> + 'static,
+{
+    let state = use_state(|| Resource::Loading);
+    let attempt = use_state(|| 0_u32);
+    let generation = use_mut_ref(|| 0_u32);
+    let load = Rc::new(load);
+
+    {
+        let state = state.clone();
+        let generation = generation.clone();
+        use_effect_with((key, *attempt), move |(key, _)| {
+            if !matches!(*state, Resource::Ready(_)) {
+                state.set(Resource::Loading);
+            }
+            let current = *generation.borrow() + 1;
+            *generation.borrow_mut() = current;
+            let future = load(key.clone());
+            spawn_local(async move {
+                let result = future.await;
+                if *generation.borrow() != current {
+                    return;
+                }
+                match result {
+                    Ok(value) => state.set(Resource::Ready(value)),
+                    Err(err) => {
+                        log_warn!("Loading failed: {:?}", err);
+                        state.set(Resource::Failed(err.to_string()));
+                    }
+                }
+            });
+        });
+    }
+
+    let retry =
+        use_callback(attempt.clone(), |_, attempt| attempt.set(**attempt + 1));
+
+    ResourceHandle {
+        state: (*state).clone(),
+        retry,
+    }
+}
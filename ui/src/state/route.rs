@@ -0,0 +1,183 @@
+use std::fmt;
+
+use chrono::{NaiveDate, Utc};
+use gloo::history::{BrowserHistory, History};
+use yew::{
+    function_component, hook, html, use_context, use_effect_with, use_state,
+    Callback, Children, ContextProvider, Html, Properties,
+};
+
+use crate::api::BASE_PATH;
+
+/// A view of the app, linkable by its path
+#[derive(Debug, Clone, PartialEq)]
+pub enum Route {
+    /// The puzzle of the day: `/`
+    Daily,
+    /// The puzzle of an earlier day: `/archive/YYYY-MM-DD`
+    Archive(NaiveDate),
+    /// Random words with undo: `/practice`
+    Practice,
+    /// A word picked by a friend: `/c/<code>`
+    Challenge(String),
+    /// The puzzle of the day with its stats open: `/stats`
+    Stats,
+}
+
+impl Route {
+    /// Parse a path below the base path. Unknown paths and
+    /// days which have not come yet show the puzzle of the day.
+    pub fn parse(path: &str) -> Route {
+        let base = BASE_PATH.unwrap_or_default().trim_end_matches('/');
+        let path = path.strip_prefix(base).unwrap_or(path);
+        let segments: Vec<&str> =
+            path.split('/').filter(|s| !s.is_empty()).collect();
+        match segments.as_slice() {
+            ["archive", date] => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .ok()
+                .filter(|date| *date <= Utc::now().date_naive())
+                .map_or(Route::Daily, Route::Archive),
+            ["practice"] => Route::Practice,
+            ["c", code] => Route::Challenge(code.to_string()),
+            ["stats"] => Route::Stats,
+            _ => Route::Daily,
+        }
+    }
+
+    /// Path of the route, including the base path
+    pub fn to_path(&self) -> String {
+        let base = BASE_PATH.unwrap_or_default().trim_end_matches('/');
+        format!("{}{}", base, self)
+    }
+
+    /// Date of the puzzle, if it is not today's
+    pub fn date(&self) -> Option<NaiveDate> {
+        match self {
+            Route::Archive(date) => Some(*date),
+            _ => None,
+        }
+    }
+
+    /// Routes which play a puzzle of the calendar
+    pub fn is_daily(&self) -> bool {
+        matches!(self, Route::Daily | Route::Archive(_) | Route::Stats)
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Route::Daily => write!(f, "/"),
+            Route::Archive(date) => {
+                write!(f, "/archive/{}", date.format("%Y-%m-%d"))
+            }
+            Route::Practice => write!(f, "/practice"),
+            Route::Challenge(code) => write!(f, "/c/{}", code),
+            Route::Stats => write!(f, "/stats"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteContext {
+    pub route: Route,
+    navigate: Callback<Route>,
+}
+
+impl RouteContext {
+    /// Go to a route, adding it to the browser history
+    pub fn navigate(&self, route: Route) {
+        self.navigate.emit(route);
+    }
+
+    /// Callback of a link to a route
+    pub fn link(&self, route: Route) -> Callback<yew::MouseEvent> {
+        let navigate = self.navigate.clone();
+        Callback::from(move |event: yew::MouseEvent| {
+            event.prevent_default();
+            navigate.emit(route.clone());
+        })
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct RouteProviderProps {
+    pub children: Children,
+}
+
+/// The route of the current location, following the
+/// back and forward buttons of the browser.
+#[function_component]
+pub fn RouteProvider(props: &RouteProviderProps) -> Html {
+    let RouteProviderProps { children } = props;
+    let history = BrowserHistory::new();
+    let route = {
+        let history = history.clone();
+        use_state(move || Route::parse(history.location().path()))
+    };
+
+    {
+        let history = history.clone();
+        let route = route.setter();
+        use_effect_with((), move |_| {
+            let listener = history.clone().listen(move || {
+                route.set(Route::parse(history.location().path()));
+            });
+            move || drop(listener)
+        });
+    }
+
+    let navigate = Callback::from(move |route: Route| {
+        history.push(route.to_path());
+    });
+
+    let ctx = RouteContext {
+        route: (*route).clone(),
+        navigate,
+    };
+
+    html! {
+        <ContextProvider<RouteContext> context={ctx}>
+            {children.clone()}
+        </ContextProvider<RouteContext>>
+    }
+}
+
+#[hook]
+pub fn use_route_context() -> RouteContext {
+    use_context::<RouteContext>().expect("RouteProvider not found")
+}
+
+#[hook]
+pub fn use_route() -> Route {
+    use_route_context().route
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_paths_of_routes() {
+        let date = NaiveDate::from_ymd_opt(2024, 4, 11).unwrap();
+        let routes = [
+            Route::Daily,
+            Route::Archive(date),
+            Route::Practice,
+            Route::Challenge("0a1b".into()),
+            Route::Stats,
+        ];
+        for route in routes {
+            assert_eq!(Route::parse(&route.to_path()), route);
+        }
+    }
+
+    #[test]
+    fn shows_the_daily_puzzle_for_unknown_paths() {
+        assert_eq!(Route::parse(""), Route::Daily);
+        assert_eq!(Route::parse("/nope"), Route::Daily);
+        assert_eq!(Route::parse("/archive/yesterday"), Route::Daily);
+        assert_eq!(Route::parse("/archive/2099-01-01"), Route::Daily);
+        assert_eq!(Route::parse("/practice/"), Route::Practice);
+    }
+}
@@ -0,0 +1,216 @@
+use gloo::{
+    events::EventListener,
+    storage::{LocalStorage, Storage},
+    utils::{document_element, window},
+};
+use serde::{Deserialize, Serialize};
+use web_sys::MediaQueryList;
+use yew::{
+    function_component, hook, html, use_callback, use_context, use_effect_with,
+    use_state, Callback, Children, ContextProvider, Html, Properties,
+};
+
+use crate::{
+    i18n::Locale,
+    state::{KeyboardLayout, Language},
+};
+
+const SETTINGS_KEY: &str = "settings";
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub enum Theme {
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+/// Media query matching a light system theme
+fn prefers_light() -> Option<MediaQueryList> {
+    window()
+        .match_media("(prefers-color-scheme: light)")
+        .ok()
+        .flatten()
+}
+
+impl Theme {
+    /// Resolve the system theme to either dark or light
+    pub fn resolve(&self) -> Theme {
+        match self {
+            Theme::System => match prefers_light() {
+                Some(query) if query.matches() => Theme::Light,
+                _ => Theme::Dark,
+            },
+            theme => theme.clone(),
+        }
+    }
+
+    pub fn to_css_class(&self) -> String {
+        match self.resolve() {
+            Theme::Light => "theme-light",
+            _ => "theme-dark",
+        }
+        .into()
+    }
+
+    /// Set the theme class on the document root
+    pub fn apply(&self) {
+        let classes = document_element().class_list();
+        let _ = classes.remove_2("theme-dark", "theme-light");
+        let _ = classes.add_1(&self.to_css_class());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub enum Palette {
+    #[default]
+    Default,
+    HighContrast,
+}
+
+impl Palette {
+    pub fn to_css_class(&self) -> String {
+        match self {
+            Palette::Default => "palette-default",
+            Palette::HighContrast => "palette-high-contrast",
+        }
+        .into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub hard_mode: bool,
+    /// Show a timer and the fastest games
+    pub speed_mode: bool,
+    pub theme: Theme,
+    pub palette: Palette,
+    pub keyboard_layout: KeyboardLayout,
+    pub animations: bool,
+    pub language: Language,
+    /// The locale of the interface, detected
+    /// from the browser if not set.
+    pub locale: Option<Locale>,
+    /// Name on the leaderboard
+    pub nickname: String,
+    /// Leaderboard the results are submitted to, none if empty
+    pub group: String,
+    /// Code of the games shared between devices, none if empty
+    pub sync_code: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            hard_mode: false,
+            speed_mode: false,
+            theme: Theme::default(),
+            palette: Palette::default(),
+            keyboard_layout: KeyboardLayout::default(),
+            animations: true,
+            language: Language::default(),
+            locale: None,
+            nickname: String::new(),
+            group: String::new(),
+            sync_code: String::new(),
+        }
+    }
+}
+
+impl Settings {
+    /// Load the settings from the local storage or
+    /// fall back to the defaults.
+    pub fn load() -> Self {
+        LocalStorage::get(SETTINGS_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        LocalStorage::set(SETTINGS_KEY, self).expect("failed to save settings");
+    }
+
+    /// Results are only shared after joining a group
+    pub fn leaderboard_enabled(&self) -> bool {
+        !self.nickname.is_empty() && !self.group.is_empty()
+    }
+
+    /// CSS classes applied to the game root
+    pub fn to_css_classes(&self) -> Vec<String> {
+        let mut classes = vec![self.palette.to_css_class()];
+        if !self.animations {
+            classes.push("no-animations".into());
+        }
+        classes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SettingsContext {
+    pub settings: Settings,
+    pub set: Callback<Settings>,
+}
+
+impl SettingsContext {
+    /// Apply a change to a copy of the current settings
+    /// and store the result.
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut Settings),
+    {
+        let mut settings = self.settings.clone();
+        f(&mut settings);
+        self.set.emit(settings);
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct SettingsProviderProps {
+    pub children: Children,
+}
+
+#[function_component]
+pub fn SettingsProvider(props: &SettingsProviderProps) -> Html {
+    let SettingsProviderProps { children } = props;
+    let settings = use_state(Settings::load);
+
+    let set = use_callback(settings.clone(), |next: Settings, settings| {
+        next.save();
+        settings.set(next);
+    });
+
+    // Apply the theme to the document and follow
+    // changes of the system theme.
+    use_effect_with(settings.theme.clone(), |theme| {
+        theme.apply();
+        let listener = match theme {
+            Theme::System => prefers_light().map(|query| {
+                EventListener::new(&query, "change", |_| {
+                    Theme::System.apply();
+                })
+            }),
+            _ => None,
+        };
+        move || drop(listener)
+    });
+
+    let ctx = SettingsContext {
+        settings: (*settings).clone(),
+        set,
+    };
+
+    html! {
+        <ContextProvider<SettingsContext> context={ctx}>
+            {children}
+        </ContextProvider<SettingsContext>>
+    }
+}
+
+#[hook]
+pub fn use_settings_context() -> SettingsContext {
+    use_context::<SettingsContext>().expect("SettingsProvider not found")
+}
+
+#[hook]
+pub fn use_settings() -> Settings {
+    use_settings_context().settings
+}
@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use gloo::{
+    events::EventListener,
+    storage::{LocalStorage, Storage},
+    utils::window,
+};
+use serde::{Deserialize, Serialize};
+use yew::{hook, use_effect_with, use_state};
+
+use crate::{
+    api,
+    debug::log_info,
+    state::{
+        get_keys, now_millis, use_game_context, use_settings, Game, GameAction,
+        State,
+    },
+    task::spawn,
+};
+
+/// Length of generated sync codes
+const SYNC_CODE_LENGTH: usize = 12;
+
+/// Shortest sync code accepted, so codes are hard to guess
+pub const MIN_SYNC_CODE_LENGTH: usize = 8;
+
+/// Games of a device, pushed to the sync server
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct SyncRequest {
+    pub games: Vec<Game>,
+}
+
+/// All games of the sync code, after merging the pushed ones
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct SyncResponse {
+    pub games: Vec<Game>,
+}
+
+impl Game {
+    /// Time of the last thing the player did, in milliseconds
+    /// since the epoch. Games predating the event log have none.
+    pub fn last_modified(&self) -> Option<i64> {
+        self.events
+            .last()
+            .map(|event| event.at)
+            .or(self.finished_at)
+    }
+}
+
+/// Merge two copies of a game from different devices: a
+/// completed game beats a running one, otherwise the one
+/// changed last wins. Ties go to the one with more guesses,
+/// then to `local`.
+pub fn merge(local: &Game, remote: &Game) -> Game {
+    let finished = |game: &Game| game.state != State::Running;
+    let winner = match (finished(local), finished(remote)) {
+        (true, false) => local,
+        (false, true) => remote,
+        _ => {
+            let key = |game: &Game| (game.last_modified(), game.guesses.len());
+            match key(remote) > key(local) {
+                true => remote,
+                false => local,
+            }
+        }
+    };
+    winner.clone()
+}
+
+/// Merge sets of games by their storage key
+pub fn merge_all(
+    local: impl IntoIterator<Item = Game>,
+    remote: impl IntoIterator<Item = Game>,
+) -> Vec<Game> {
+    let mut games: BTreeMap<String, Game> = local
+        .into_iter()
+        .map(|game| (game.storage_key(), game))
+        .collect();
+    for game in remote {
+        let merged = match games.get(&game.storage_key()) {
+            Some(local) => merge(local, &game),
+            None => game,
+        };
+        games.insert(merged.storage_key(), merged);
+    }
+    games.into_values().collect()
+}
+
+/// All stored games, of every namespace
+pub fn stored_games() -> Vec<Game> {
+    get_keys("game:")
+        .into_iter()
+        .filter_map(|key| LocalStorage::get(key).ok())
+        .collect()
+}
+
+/// Merge games into the stored ones, which may have changed
+/// while syncing. Returns the number of games changed.
+pub fn store_games(games: &[Game]) -> usize {
+    games
+        .iter()
+        .filter_map(|game| {
+            let stored: Option<Game> =
+                LocalStorage::get(game.storage_key()).ok();
+            let merged = match &stored {
+                Some(stored) => merge(stored, game),
+                None => game.clone(),
+            };
+            (stored.as_ref() != Some(&merged)).then_some(merged)
+        })
+        .inspect(Game::save)
+        .count()
+}
+
+/// A new random sync code
+pub fn new_sync_code() -> String {
+    const LETTERS: &[u8] = b"abcdefghijkmnpqrstuvwxyz23456789";
+    (0..SYNC_CODE_LENGTH)
+        .map(|_| {
+            let index = js_sys::Math::random() * LETTERS.len() as f64;
+            LETTERS[index as usize % LETTERS.len()] as char
+        })
+        .collect()
+}
+
+/// Games are synced by the API server
+pub fn sync_available() -> bool {
+    api::server_mode()
+}
+
+/// Sync the stored games with the other devices of the sync
+/// code: when the app starts or regains focus, and after
+/// every guess.
+#[hook]
+pub fn use_sync() {
+    let ctx = use_game_context();
+    let code = use_settings().sync_code;
+    // Time the window was focused last
+    let focused = use_state(now_millis);
+
+    {
+        let focused = focused.setter();
+        use_effect_with((), move |_| {
+            let listener = EventListener::new(&window(), "focus", move |_| {
+                focused.set(now_millis())
+            });
+            move || drop(listener)
+        });
+    }
+
+    let game = &ctx.game;
+    let deps = (
+        code,
+        game.storage_key(),
+        game.guesses.len(),
+        game.state.clone(),
+        *focused,
+    );
+    let dispatch = ctx.dispatch.clone();
+    use_effect_with(deps, move |(code, key, ..)| {
+        if !sync_available() || code.is_empty() {
+            return;
+        }
+        let code = code.clone();
+        let key = key.clone();
+        spawn(async move {
+            let games = api::ApiClient::default()
+                .sync(&code, stored_games())
+                .await?;
+            let changed = store_games(&games);
+            if changed > 0 {
+                log_info!("Synced {} games", changed);
+            }
+            if let Some(game) =
+                games.into_iter().find(|g| g.storage_key() == key)
+            {
+                dispatch.emit(GameAction::Synced(game));
+            }
+            Ok(())
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{GameEvent, GameEventKind, Guess};
+
+    /// A game with a guess per word, changed last at `at`.
+    /// Games are not updated, which would store them.
+    fn game(id: u32, words: &[&str], at: i64) -> Game {
+        let guesses: Vec<Guess> = words.iter().map(|&w| w.into()).collect();
+        let events = guesses
+            .iter()
+            .map(|guess| GameEvent {
+                at,
+                kind: GameEventKind::Submitted(guess.clone()),
+            })
+            .collect();
+        let state = match words.last() {
+            Some(&"BROTH") => State::Win,
+            _ => State::Running,
+        };
+        Game {
+            id,
+            solution: "BROTH".into(),
+            guesses,
+            events,
+            state,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn completed_beats_running() {
+        let finished = game(1, &["CRANE", "BROTH"], 10);
+        let running = game(1, &["CRANE", "PLUMB"], 20);
+        assert_eq!(merge(&running, &finished), finished);
+        assert_eq!(merge(&finished, &running), finished);
+    }
+
+    #[test]
+    fn last_writer_wins() {
+        let older = game(1, &["CRANE", "PLUMB"], 10);
+        let newer = game(1, &["CRANE"], 20);
+        assert_eq!(merge(&older, &newer), newer);
+        assert_eq!(merge(&newer, &older), newer);
+    }
+
+    #[test]
+    fn ties_go_to_more_guesses_then_local() {
+        let one = game(1, &["CRANE"], 10);
+        let two = game(1, &["CRANE", "PLUMB"], 10);
+        assert_eq!(merge(&one, &two), two);
+        let other = game(1, &["PLUMB"], 10);
+        assert_eq!(merge(&one, &other), one);
+    }
+
+    #[test]
+    fn merges_games_by_key() {
+        let local = vec![game(1, &["CRANE"], 10), game(2, &["CRANE"], 10)];
+        let remote = vec![game(2, &["CRANE", "PLUMB"], 20), game(3, &[], 0)];
+        let games = merge_all(local, remote);
+        let ids: Vec<u32> = games.iter().map(|game| game.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(games[1].tries(), 2);
+    }
+}
@@ -1,30 +1,114 @@
+use std::rc::Rc;
+
 use yew::{
     function_component, hook, html, use_context, use_effect_with, use_state,
     Children, ContextProvider, Html, Properties,
 };
 
-use crate::{api, task::spawn};
+pub use crate::encoding::content_hash;
+use crate::{
+    api, encoding,
+    state::{normalize, use_language, Language},
+    task::spawn,
+};
+
+/// Encoded word lists and their versions, embedded by the build script
+fn embedded_data(language: &Language) -> (&'static [u8], &'static str) {
+    macro_rules! wordlist {
+        ($code:literal) => {
+            (
+                include_bytes!(concat!(
+                    env!("OUT_DIR"),
+                    "/words-",
+                    $code,
+                    ".bin"
+                )),
+                include_str!(concat!(
+                    env!("OUT_DIR"),
+                    "/words-",
+                    $code,
+                    ".hash"
+                )),
+            )
+        };
+    }
+    match language {
+        Language::English => wordlist!("en"),
+        Language::German => wordlist!("de"),
+        Language::Spanish => wordlist!("es"),
+    }
+}
+
+/// Sort the words, so they can be looked up by binary search
+fn sorted(mut words: Vec<String>) -> Rc<Vec<String>> {
+    words.sort_unstable();
+    Rc::new(words)
+}
 
 #[derive(Clone, PartialEq, Default)]
 pub struct Wordlist {
-    words: Vec<String>,
+    /// Normalized and sorted
+    words: Rc<Vec<String>>,
+    /// Hash of the text the list was built from
+    version: String,
 }
 
 impl Wordlist {
     pub fn new(words: Vec<String>) -> Self {
-        Self { words }
+        Self {
+            words: sorted(words),
+            version: String::new(),
+        }
+    }
+
+    /// The word list bundled with the app
+    pub fn embedded(language: &Language) -> Self {
+        let (bytes, version) = embedded_data(language);
+        let words = encoding::decode(bytes)
+            .expect("corrupt embedded word list")
+            .iter()
+            .map(|word| normalize(word))
+            .collect();
+        Self {
+            words: sorted(words),
+            version: version.into(),
+        }
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The normalized words in sorted order
+    pub fn words(&self) -> &[String] {
+        &self.words
     }
 
     pub fn contains(&self, word: &str) -> bool {
-        let word = word.to_lowercase();
-        self.words.contains(&word)
+        let word = normalize(word);
+        self.words.binary_search(&word).is_ok()
     }
 }
 
 impl From<String> for Wordlist {
     fn from(text: String) -> Self {
-        let words = text.lines().map(|s| s.to_string()).collect();
-        Self { words }
+        let words = text
+            .lines()
+            .filter(|s| !s.trim().is_empty())
+            .map(normalize)
+            .collect();
+        Self {
+            words: sorted(words),
+            version: content_hash(&text),
+        }
     }
 }
 
@@ -36,27 +120,34 @@ pub struct WordlistProviderProps {
 #[function_component]
 pub fn WordlistProvider(props: &WordlistProviderProps) -> Html {
     let WordlistProviderProps { children } = props;
-    let wordlist = use_state(Wordlist::default);
-    let is_ready = use_state(|| false);
+    let language = use_language();
+    // The embedded list is available right away, so the board
+    // never waits for a download.
+    let wordlist = use_state(|| Wordlist::embedded(&language));
 
     {
-        let is_ready = is_ready.clone();
         let wordlist = wordlist.clone();
-        use_effect_with((), move |_| {
-            spawn(async move {
-                let words = api::load_wordlist().await?;
-                wordlist.set(words);
-                is_ready.set(true);
-                Ok(())
-            });
+        use_effect_with(language, move |language| {
+            let embedded = Wordlist::embedded(language);
+            let version = embedded.version().to_string();
+            wordlist.set(embedded);
+            // The API may serve a newer list
+            if api::server_mode() {
+                let language = language.clone();
+                spawn(async move {
+                    if let Some(words) = api::ApiClient::default()
+                        .refresh_wordlist(&language, &version)
+                        .await?
+                    {
+                        wordlist.set(words);
+                    }
+                    Ok(())
+                });
+            }
             || {}
         });
     }
 
-    if !(*is_ready) {
-        return html! { "Loading Words..." };
-    }
-
     html! {
         <ContextProvider<Wordlist> context={(*wordlist).clone()}>
             {children}
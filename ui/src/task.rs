@@ -4,11 +4,12 @@ use std::future::Future;
 use anyhow::Result;
 use wasm_bindgen_futures::spawn_local;
 
-use crate::debug::log;
+use crate::debug::log_error;
 
 
 // Spawn an async task on the current thread.
-// The task must return an anyhow Result.
+// The task must return an anyhow Result, errors
+// are logged and reported.
 pub fn spawn<F>(future: F)
 where
     F: Future<Output = Result<()>> + 'static,
@@ -16,7 +17,7 @@ where
     spawn_local(async move {
         match future.await {
             Ok(()) => {}
-            Err(err) => log!("Failed: {:?}", err),
+            Err(err) => log_error!("Task failed: {:?}", err),
         }
     });
 }
@@ -0,0 +1,222 @@
+//! Checks for the bundled data: the word list and the
+//! puzzle files of every language pack.
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::Path,
+};
+
+use chrono::NaiveDate;
+
+use crate::{
+    api::Wordle,
+    state::{normalize, Language, Wordlist},
+};
+
+/// Number of letters of every word
+pub const WORD_LENGTH: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub file: String,
+    pub message: String,
+}
+
+impl Problem {
+    fn new(file: &str, message: impl Into<String>) -> Self {
+        Problem {
+            file: file.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.file, self.message)
+    }
+}
+
+/// Letters allowed in the word list of a language
+fn is_letter(language: &Language, c: char) -> bool {
+    match language {
+        Language::English => c.is_ascii_lowercase(),
+        _ => c.is_alphabetic() && c.is_lowercase(),
+    }
+}
+
+/// Check that every word is lowercase, has the right
+/// length and only appears once.
+pub fn validate_wordlist(
+    file: &str,
+    text: &str,
+    language: &Language,
+) -> Vec<Problem> {
+    let mut problems = vec![];
+    for (n, line) in text.lines().enumerate() {
+        let word = line.trim();
+        if word.is_empty() {
+            continue;
+        }
+        let at = |message: String| {
+            Problem::new(file, format!("line {}: {}", n + 1, message))
+        };
+        if !word.chars().all(|c| is_letter(language, c)) {
+            problems.push(at(format!("invalid letters in {:?}", word)));
+        }
+        let length = normalize(word).chars().count();
+        if length != WORD_LENGTH {
+            problems.push(at(format!("{:?} has {} letters", word, length)));
+        }
+    }
+
+    let wordlist = Wordlist::from(text.to_string());
+    let mut seen = HashSet::new();
+    for word in wordlist.words() {
+        if !seen.insert(word) {
+            problems.push(Problem::new(file, format!("duplicate {:?}", word)));
+        }
+    }
+    problems
+}
+
+/// Check the puzzle files, given as file name and content:
+/// the date must match the file name, the solution must be
+/// in the word list, ids and dates are never repeated and
+/// ids and days advance with the date of the file name.
+pub fn validate_puzzles(
+    files: &[(String, String)],
+    wordlist: &Wordlist,
+    language: &Language,
+) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut puzzles: Vec<(String, NaiveDate, Wordle)> = vec![];
+    for (file, text) in files {
+        let wordle: Wordle = match serde_json::from_str(text) {
+            Ok(wordle) => wordle,
+            Err(err) => {
+                problems.push(Problem::new(file, err.to_string()));
+                continue;
+            }
+        };
+        if file.as_str() != format!("{}.json", wordle.print_date) {
+            problems.push(Problem::new(
+                file,
+                format!("print_date {} does not match", wordle.print_date),
+            ));
+        }
+        if !wordlist.contains(&language.fold(&wordle.solution)) {
+            problems.push(Problem::new(
+                file,
+                format!(
+                    "solution {:?} is not in the word list",
+                    wordle.solution
+                ),
+            ));
+        }
+        let name = file.strip_suffix(".json").unwrap_or(file);
+        match NaiveDate::parse_from_str(name, "%Y-%m-%d") {
+            Ok(date) => puzzles.push((file.clone(), date, wordle)),
+            Err(_) => problems.push(Problem::new(file, "name is not a date")),
+        }
+    }
+
+    puzzles.sort_by_key(|(_, date, _)| *date);
+    let mut ids = HashMap::new();
+    let mut dates = HashMap::new();
+    for (file, _, wordle) in &puzzles {
+        if let Some(first) = ids.insert(wordle.id, file) {
+            problems.push(Problem::new(
+                file,
+                format!("id {} is already used by {}", wordle.id, first),
+            ));
+        }
+        if let Some(first) = dates.insert(&wordle.print_date, file) {
+            problems.push(Problem::new(
+                file,
+                format!(
+                    "print_date {} is already used by {}",
+                    wordle.print_date, first
+                ),
+            ));
+        }
+    }
+    for pair in puzzles.windows(2) {
+        let [(_, prev_date, prev), (file, date, wordle)] = pair else {
+            continue;
+        };
+        let days = (*date - *prev_date).num_days();
+        if wordle.id as i64 - prev.id as i64 != days {
+            problems.push(Problem::new(
+                file,
+                format!("id {} does not follow {}", wordle.id, prev.id),
+            ));
+        }
+        let since_launch =
+            wordle.days_since_launch as i64 - prev.days_since_launch as i64;
+        if since_launch != days {
+            problems.push(Problem::new(
+                file,
+                format!(
+                    "days_since_launch {} does not follow {}",
+                    wordle.days_since_launch, prev.days_since_launch
+                ),
+            ));
+        }
+    }
+    problems
+}
+
+/// Validate the data of a language pack, found in
+/// its data path below `root`.
+pub fn validate_language(root: &Path, language: &Language) -> Vec<Problem> {
+    let dir = root.join(language.data_path());
+    let display = |name: &str| dir.join(name).display().to_string();
+
+    let words = display("words.txt");
+    let text = match std::fs::read_to_string(&words) {
+        Ok(text) => text,
+        Err(err) => return vec![Problem::new(&words, err.to_string())],
+    };
+    let mut problems = validate_wordlist(&words, &text, language);
+    let wordlist = Wordlist::from(text);
+
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            problems.push(Problem::new(&display(""), err.to_string()));
+            return problems;
+        }
+    };
+    let mut files = vec![];
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.ends_with(".json") {
+            continue;
+        }
+        match std::fs::read_to_string(entry.path()) {
+            Ok(text) => files.push((name, text)),
+            Err(err) => {
+                problems.push(Problem::new(&display(&name), err.to_string()))
+            }
+        }
+    }
+    files.sort();
+    problems.extend(
+        validate_puzzles(&files, &wordlist, language)
+            .into_iter()
+            .map(|problem| Problem {
+                file: display(&problem.file),
+                ..problem
+            }),
+    );
+    problems
+}
+
+/// Validate all language packs
+pub fn validate_all(root: &Path) -> Vec<Problem> {
+    Language::all()
+        .iter()
+        .flat_map(|language| validate_language(root, language))
+        .collect()
+}
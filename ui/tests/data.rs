@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use nerdle_ui::{
+    state::{Language, Wordlist},
+    validate::{validate_all, validate_puzzles, validate_wordlist},
+};
+
+/// Problems of english day files as they were published. They
+/// stay until corrected files come from the puzzle source, as
+/// changing an id would orphan the games stored under it.
+const KNOWN_PROBLEMS: [&str; 16] = [
+    "data/2024-04-14.json: print_date 2024-04-11 does not match",
+    "data/2024-04-16.json: print_date 2024-04-10 does not match",
+    "data/2024-04-17.json: print_date 2024-04-10 does not match",
+    "data/2024-04-14.json: id 516 is already used by 2024-04-11.json",
+    "data/2024-04-14.json: print_date 2024-04-11 is already used by 2024-04-11.json",
+    "data/2024-04-16.json: id 2149 is already used by 2024-04-10.json",
+    "data/2024-04-16.json: print_date 2024-04-10 is already used by 2024-04-10.json",
+    "data/2024-04-17.json: id 2149 is already used by 2024-04-16.json",
+    "data/2024-04-17.json: print_date 2024-04-10 is already used by 2024-04-16.json",
+    "data/2024-04-11.json: id 516 does not follow 2149",
+    "data/2024-04-14.json: id 516 does not follow 516",
+    "data/2024-04-14.json: days_since_launch 1027 does not follow 1027",
+    "data/2024-04-16.json: id 2149 does not follow 516",
+    "data/2024-04-16.json: days_since_launch 1026 does not follow 1027",
+    "data/2024-04-17.json: id 2149 does not follow 2149",
+    "data/2024-04-17.json: days_since_launch 1026 does not follow 1026",
+];
+
+#[test]
+fn bundled_data_is_valid() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let problems: Vec<String> = validate_all(root)
+        .iter()
+        .map(|problem| problem.to_string())
+        .collect();
+    for known in KNOWN_PROBLEMS {
+        assert!(
+            problems.iter().any(|problem| problem.ends_with(known)),
+            "fixed, remove from the known problems: {}",
+            known
+        );
+    }
+    let report = problems
+        .iter()
+        .filter(|problem| {
+            !KNOWN_PROBLEMS.iter().any(|known| problem.ends_with(known))
+        })
+        .cloned()
+        .collect::<Vec<String>>()
+        .join("\n");
+    assert!(report.is_empty(), "invalid data:\n{}", report);
+}
+
+#[test]
+fn reports_every_wordlist_problem() {
+    let text = "crane\nCrane\nsmall\nwords\nwords\ncañon\n";
+    let problems = validate_wordlist("words.txt", text, &Language::English);
+    let messages: Vec<String> = problems
+        .iter()
+        .map(|problem| problem.message.clone())
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "line 2: invalid letters in \"Crane\"",
+            "line 6: invalid letters in \"cañon\"",
+            "duplicate \"CRANE\"",
+            "duplicate \"WORDS\"",
+        ]
+    );
+    let problems =
+        validate_wordlist("words.txt", "cañon\n", &Language::Spanish);
+    assert!(problems.is_empty());
+}
+
+#[test]
+fn reports_every_puzzle_problem() {
+    let wordlist = Wordlist::from("crane\nbroth\n".to_string());
+    let files = vec![
+        (
+            "2024-04-10.json".to_string(),
+            r#"{"id":1,"solution":"crane","print_date":"2024-04-10","days_since_launch":0,"editor":"nerdle"}"#.to_string(),
+        ),
+        (
+            "2024-04-12.json".to_string(),
+            r#"{"id":3,"solution":"zebra","print_date":"2024-04-11","days_since_launch":2,"editor":"nerdle"}"#.to_string(),
+        ),
+        ("2024-04-13.json".to_string(), "{".to_string()),
+        (
+            "2024-04-14.json".to_string(),
+            r#"{"id":3,"solution":"broth","print_date":"2024-04-11","days_since_launch":4,"editor":"nerdle"}"#.to_string(),
+        ),
+    ];
+    let problems = validate_puzzles(&files, &wordlist, &Language::English);
+    let problems: Vec<String> =
+        problems.iter().map(|problem| problem.to_string()).collect();
+    assert!(problems[2].starts_with("2024-04-13.json: "));
+    assert_eq!(
+        [&problems[..2], &problems[3..]].concat(),
+        [
+            "2024-04-12.json: print_date 2024-04-11 does not match",
+            "2024-04-12.json: solution \"zebra\" is not in the word list",
+            "2024-04-14.json: print_date 2024-04-11 does not match",
+            "2024-04-14.json: id 3 is already used by 2024-04-12.json",
+            "2024-04-14.json: print_date 2024-04-11 is already used by 2024-04-12.json",
+            "2024-04-14.json: id 3 does not follow 3",
+        ]
+    );
+}
+
+#[test]
+fn embedded_wordlists_match_the_text() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for language in Language::all() {
+        let path = root.join(language.data_path()).join("words.txt");
+        let text = std::fs::read_to_string(path).unwrap();
+        let wordlist = Wordlist::from(text);
+        let embedded = Wordlist::embedded(&language);
+        assert_eq!(embedded.version(), wordlist.version());
+        assert_eq!(embedded.len(), wordlist.len());
+        assert!(wordlist.words().iter().all(|word| embedded.contains(word)));
+    }
+}